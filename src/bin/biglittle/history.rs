@@ -0,0 +1,85 @@
+//! Matching History Files
+//!
+//! The history of past seasons is kept in a TOML, YAML, or JSON file, chosen by its extension,
+//! and every run with a history file appends its matching as the newest season.
+
+use anyhow::{anyhow, bail, Result};
+use biglittle::history::{History, RepeatPolicy};
+use std::{ffi::OsStr, fs, path::Path};
+
+/// Parses a [`RepeatPolicy`] from its command-line form.
+#[inline]
+pub fn repeat_policy(s: &str) -> Result<RepeatPolicy> {
+    match s {
+        "forbid" => Ok(RepeatPolicy::Forbid),
+        "penalize" => Ok(RepeatPolicy::Penalize),
+        _ => bail!("Unknown repeat policy `{s}`, expected `forbid` or `penalize`."),
+    }
+}
+
+/// Reads the history file at `path`, or returns an empty history if it does not exist yet.
+#[inline]
+pub fn read_history(path: &Path) -> Result<History> {
+    if !path.exists() {
+        return Ok(History::default());
+    }
+    let contents = fs::read_to_string(path)
+        .map_err(|err| anyhow!("Unable to read history file {}: {err}.", path.display()))?;
+    Ok(match path.extension().and_then(OsStr::to_str) {
+        Some("toml") => toml::from_str(&contents)?,
+        Some("yaml" | "yml") => serde_yaml::from_str(&contents)?,
+        Some("json") => serde_json::from_str(&contents)?,
+        Some(ext) => bail!("Unrecognized history file format: {ext}."),
+        _ => bail!("Unable to parse history file path: {}.", path.display()),
+    })
+}
+
+/// Writes the `history` to the file at `path` in the format given by its extension.
+#[inline]
+pub fn write_history(path: &Path, history: &History) -> Result<()> {
+    let contents = match path.extension().and_then(OsStr::to_str) {
+        Some("toml") => toml::to_string(history)?,
+        Some("yaml" | "yml") => serde_yaml::to_string(history)?,
+        Some("json") => serde_json::to_string_pretty(history)?,
+        Some(ext) => bail!("Unrecognized history file format: {ext}."),
+        _ => bail!("Unable to parse history file path: {}.", path.display()),
+    };
+    fs::write(path, contents)
+        .map_err(|err| anyhow!("Unable to write history file {}: {err}.", path.display()))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use biglittle::history::Season;
+
+    /// Checks that a history survives a round trip through every file format.
+    #[test]
+    fn history_formats() {
+        let mut history = History::default();
+        history.push(Season {
+            label: "Fall".to_string(),
+            pairs: vec![
+                ("Alice".to_string(), "Dan".to_string()),
+                ("Bob".to_string(), "Erin".to_string()),
+            ],
+        });
+        history.push(Season {
+            label: "Spring".to_string(),
+            pairs: Vec::new(),
+        });
+        let directory = std::env::temp_dir().join(format!("biglittle-{}", std::process::id()));
+        fs::create_dir_all(&directory).unwrap();
+        for extension in ["toml", "yaml", "json"] {
+            let path = directory.join(format!("history.{extension}"));
+            write_history(&path, &history).unwrap();
+            assert_eq!(read_history(&path).unwrap(), history);
+        }
+        fs::remove_dir_all(&directory).unwrap();
+        assert_eq!(
+            read_history(&directory.join("missing.toml")).unwrap(),
+            History::default()
+        );
+        assert!(repeat_policy("sometimes").is_err());
+    }
+}
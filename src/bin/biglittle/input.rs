@@ -0,0 +1,556 @@
+//! CSV Input
+
+use anyhow::{anyhow, bail, ensure, Result};
+use biglittle::{
+    metadata::Metadata, Big, DynamicKind, Index, Kind, Little, Names, PreferenceTable,
+};
+use csv::{Reader, ReaderBuilder, StringRecord, Trim};
+use indexmap::IndexMap;
+use serde::Deserialize;
+use std::{ffi::OsStr, fs::File, path::PathBuf, str::FromStr};
+
+/// Checks that the input has the correct extension for CSV parsing.
+#[inline]
+fn check_input_extension(path: PathBuf) -> Result<PathBuf> {
+    match path.extension().and_then(OsStr::to_str) {
+        Some("csv") => Ok(path),
+        Some(ext) => bail!("Unrecognized input file format: {ext}."),
+        _ => bail!("Unable to parse input path: {}.", path.display()),
+    }
+}
+
+/// Returns a CSV reader for `path`.
+#[inline]
+pub fn reader(path: PathBuf) -> Result<Reader<File>> {
+    Ok(ReaderBuilder::new()
+        .flexible(true)
+        .trim(Trim::All)
+        .from_path(check_input_extension(path)?)?)
+}
+
+/// Column Selector
+///
+/// Selects columns either by a header pattern, where `*` matches any sequence of characters, or by
+/// an inclusive range of one-based column positions like `3-7` or `3-`.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq)]
+#[serde(try_from = "String")]
+pub enum ColumnSelector {
+    /// Header Pattern
+    Pattern(String),
+
+    /// Column Position Range
+    Range {
+        /// First Column Position
+        start: usize,
+
+        /// Last Column Position
+        end: Option<usize>,
+    },
+}
+
+impl ColumnSelector {
+    /// Returns `true` if the column at the zero-based `position` with the given `header` is
+    /// selected by `self`.
+    #[inline]
+    pub fn matches(&self, position: usize, header: &str) -> bool {
+        match self {
+            Self::Pattern(pattern) => matches_pattern(pattern, header),
+            Self::Range { start, end } => {
+                let position = position + 1;
+                *start <= position && end.is_none_or(|end| position <= end)
+            }
+        }
+    }
+}
+
+impl FromStr for ColumnSelector {
+    type Err = anyhow::Error;
+
+    #[inline]
+    fn from_str(s: &str) -> Result<Self> {
+        let range = s.split_once('-').and_then(|(start, end)| {
+            let start = start.trim().parse::<usize>().ok()?;
+            let end = end.trim();
+            if end.is_empty() {
+                Some((start, None))
+            } else {
+                Some((start, Some(end.parse::<usize>().ok()?)))
+            }
+        });
+        match range {
+            Some((start, end)) => {
+                ensure!(start > 0, "Column positions start at 1.");
+                ensure!(
+                    end.is_none_or(|end| start <= end),
+                    "Column range `{s}` is empty."
+                );
+                Ok(Self::Range { start, end })
+            }
+            _ => Ok(Self::Pattern(s.to_string())),
+        }
+    }
+}
+
+impl TryFrom<String> for ColumnSelector {
+    type Error = anyhow::Error;
+
+    #[inline]
+    fn try_from(s: String) -> Result<Self> {
+        s.parse()
+    }
+}
+
+/// Returns `true` if `header` matches `pattern`, where `*` matches any sequence of characters.
+#[inline]
+fn matches_pattern(pattern: &str, header: &str) -> bool {
+    let mut parts = pattern.split('*');
+    let first = parts.next().unwrap_or_default();
+    let Some(mut rest) = header.strip_prefix(first) else {
+        return false;
+    };
+    let parts = parts.collect::<Vec<_>>();
+    let Some((last, middle)) = parts.split_last() else {
+        return rest.is_empty();
+    };
+    for part in middle {
+        match rest.find(part) {
+            Some(index) => rest = &rest[index + part.len()..],
+            _ => return false,
+        }
+    }
+    rest.len() >= last.len() && rest.ends_with(last)
+}
+
+/// Column Mapping
+///
+/// Describes which columns of an input file hold the participant key, their ranks, and their
+/// metadata. By default, every column after the key column is a rank and every column before it
+/// is metadata.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ColumnMapping {
+    /// Key Column Header
+    pub key: String,
+
+    /// Role Column Header
+    ///
+    /// Only used for combined inputs where bigs and littles are in the same file.
+    pub role: Option<String>,
+
+    /// Rank Column Selectors
+    pub ranks: Vec<ColumnSelector>,
+
+    /// Ignored Column Selectors
+    pub ignore: Vec<ColumnSelector>,
+
+    /// Metadata Column Selectors
+    pub metadata: Vec<ColumnSelector>,
+}
+
+impl Default for ColumnMapping {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            key: "Name".to_string(),
+            role: None,
+            ranks: Vec::new(),
+            ignore: Vec::new(),
+            metadata: Vec::new(),
+        }
+    }
+}
+
+/// Column Role
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Column {
+    /// Key Column
+    Key,
+
+    /// Role Column
+    Role,
+
+    /// Rank Column
+    Rank,
+
+    /// Metadata Column
+    Metadata,
+
+    /// Ignored Column
+    Ignored,
+}
+
+impl ColumnMapping {
+    /// Assigns a [`Column`] role to each of the `headers`.
+    #[inline]
+    pub fn columns(&self, headers: &StringRecord) -> Result<Vec<Column>> {
+        let key = headers
+            .iter()
+            .position(|h| h == self.key)
+            .ok_or_else(|| anyhow!("Missing `{}` header.", self.key))?;
+        let role = match &self.role {
+            Some(role) => Some(
+                headers
+                    .iter()
+                    .position(|h| h == role)
+                    .ok_or_else(|| anyhow!("Missing `{role}` header."))?,
+            ),
+            _ => None,
+        };
+        headers
+            .iter()
+            .enumerate()
+            .map(|(position, header)| {
+                let selected = |selectors: &[ColumnSelector]| {
+                    selectors.iter().any(|s| s.matches(position, header))
+                };
+                if position == key {
+                    return Ok(Column::Key);
+                }
+                if Some(position) == role {
+                    return Ok(Column::Role);
+                }
+                if selected(&self.ignore) {
+                    return Ok(Column::Ignored);
+                }
+                let is_rank = if self.ranks.is_empty() {
+                    position > key
+                } else {
+                    selected(&self.ranks)
+                };
+                let is_metadata = if self.metadata.is_empty() {
+                    position < key
+                } else {
+                    selected(&self.metadata)
+                };
+                match (is_rank, is_metadata) {
+                    (true, true) => {
+                        bail!("Column `{header}` is selected as a rank and as metadata.")
+                    }
+                    (true, _) => Ok(Column::Rank),
+                    (_, true) => Ok(Column::Metadata),
+                    _ => Ok(Column::Ignored),
+                }
+            })
+            .collect()
+    }
+}
+
+/// Participant Record
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Record {
+    /// Role
+    pub role: Option<String>,
+
+    /// Metadata Fields
+    pub metadata: Vec<(String, String)>,
+
+    /// Preferences
+    pub preferences: Vec<String>,
+}
+
+/// Role Values
+///
+/// The values of the role column which mark a row as a big or as a little, compared without
+/// regard to case.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Roles {
+    /// Big Role Value
+    pub big: String,
+
+    /// Little Role Value
+    pub little: String,
+}
+
+impl Roles {
+    /// Returns the kind of participant marked by the role `value`.
+    #[inline]
+    pub fn kind(&self, value: &str) -> Result<DynamicKind> {
+        if value.eq_ignore_ascii_case(&self.big) {
+            Ok(DynamicKind::Big)
+        } else if value.eq_ignore_ascii_case(&self.little) {
+            Ok(DynamicKind::Little)
+        } else {
+            bail!(
+                "Unknown role `{value}`, expected `{}` or `{}`.",
+                self.big,
+                self.little
+            )
+        }
+    }
+}
+
+impl Default for Roles {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            big: "Big".to_string(),
+            little: "Little".to_string(),
+        }
+    }
+}
+
+/// Loads the records from `reader` keyed by the entries in the key column of the `mapping`.
+#[inline]
+pub fn load_from_reader(
+    mut reader: Reader<File>,
+    mapping: &ColumnMapping,
+) -> Result<IndexMap<String, Record>> {
+    let headers = reader.headers()?.clone();
+    let columns = mapping.columns(&headers)?;
+    let mut records = IndexMap::default();
+    for record in reader.records() {
+        let record = record?;
+        let mut key = None;
+        let mut parsed = Record::default();
+        for ((column, header), value) in columns.iter().zip(headers.iter()).zip(record.iter()) {
+            match column {
+                Column::Key => key = Some(value),
+                Column::Role => parsed.role = Some(value.to_string()),
+                Column::Rank if !value.is_empty() => parsed.preferences.push(value.to_string()),
+                Column::Metadata if !value.is_empty() => parsed
+                    .metadata
+                    .push((header.to_string(), value.to_string())),
+                _ => {}
+            }
+        }
+        let key = key
+            .filter(|k| !k.is_empty())
+            .ok_or_else(|| anyhow!("Missing `{}` record.", mapping.key))?;
+        ensure!(
+            records.insert(key.to_string(), parsed).is_none(),
+            "Duplicate `{}` record: {key}.",
+            mapping.key
+        );
+    }
+    Ok(records)
+}
+
+/// Inserts `name` into the `names` table returning its index.
+#[inline]
+pub fn insert_name<K>(names: &mut Names, name: &str) -> Result<Index<K>>
+where
+    K: Kind,
+{
+    names.insert::<K>(name.to_string()).ok_or_else(|| {
+        anyhow!(
+            "Unable to insert {name} into the {:?} name table.",
+            K::dynamic()
+        )
+    })
+}
+
+/// Gets the index of `name` from the `names` table.
+#[inline]
+pub fn get_index<K>(names: &Names, name: &str) -> Result<Index<K>>
+where
+    K: Kind,
+{
+    names.index::<K>(name).ok_or_else(|| {
+        anyhow!(
+            "Unable to get {name} from the {:?} name table.",
+            K::dynamic()
+        )
+    })
+}
+
+/// Inserts the names and metadata from `records` with the kind `K`.
+#[inline]
+fn insert_records<K>(
+    names: &mut Names,
+    metadata: &mut Metadata,
+    records: &IndexMap<String, Record>,
+) -> Result<()>
+where
+    K: Kind,
+{
+    for (name, record) in records {
+        let index = insert_name::<K>(names, name)?;
+        for (field, value) in &record.metadata {
+            metadata.insert(index, field.clone(), value.clone());
+        }
+    }
+    Ok(())
+}
+
+/// Inserts the preferences from `records` with the kind `K` into `table`.
+#[inline]
+fn insert_preferences<K>(
+    names: &Names,
+    table: &mut PreferenceTable,
+    records: IndexMap<String, Record>,
+) -> Result<()>
+where
+    K: Kind,
+{
+    for (_, record) in records {
+        table.insert::<K, _>(
+            record
+                .preferences
+                .iter()
+                .map(|n| get_index(names, n))
+                .collect::<Result<Vec<_>>>()?,
+        );
+    }
+    Ok(())
+}
+
+/// Shuffles the order of the `records` using a SplitMix64 generator which advances `state`.
+#[inline]
+pub fn shuffle(records: IndexMap<String, Record>, state: &mut u64) -> IndexMap<String, Record> {
+    let mut records = records.into_iter().collect::<Vec<_>>();
+    for i in (1..records.len()).rev() {
+        *state = state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = *state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^= z >> 31;
+        records.swap(i, (z % (i as u64 + 1)) as usize);
+    }
+    records.into_iter().collect()
+}
+
+/// Builds the names, preferences, and metadata from the `bigs` and `littles` records.
+#[inline]
+pub fn build(
+    bigs: IndexMap<String, Record>,
+    littles: IndexMap<String, Record>,
+) -> Result<(Names, PreferenceTable, Metadata)> {
+    let mut names = Names::default();
+    let mut metadata = Metadata::default();
+    let mut table = PreferenceTable::default();
+    insert_records::<Big>(&mut names, &mut metadata, &bigs)?;
+    insert_records::<Little>(&mut names, &mut metadata, &littles)?;
+    insert_preferences::<Big>(&names, &mut table, bigs)?;
+    insert_preferences::<Little>(&names, &mut table, littles)?;
+    Ok((names, table, metadata))
+}
+
+/// Splits the `records` of a combined input into big and little records by their role, which
+/// must be one of the `roles`.
+#[inline]
+pub fn split_roles(
+    records: IndexMap<String, Record>,
+    roles: &Roles,
+) -> Result<(IndexMap<String, Record>, IndexMap<String, Record>)> {
+    let mut bigs = IndexMap::new();
+    let mut littles = IndexMap::new();
+    for (name, record) in records {
+        match roles.kind(record.role.as_deref().unwrap_or_default())? {
+            DynamicKind::Big => bigs.insert(name, record),
+            DynamicKind::Little => littles.insert(name, record),
+        };
+    }
+    Ok((bigs, littles))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    /// Returns the roles assigned by `mapping` to the comma-separated `headers`.
+    fn columns(mapping: &ColumnMapping, headers: &str) -> Result<Vec<Column>> {
+        mapping.columns(&StringRecord::from(headers.split(',').collect::<Vec<_>>()))
+    }
+
+    /// Checks the header patterns.
+    #[test]
+    fn header_patterns() {
+        assert!(matches_pattern("Rank*", "Rank 1"));
+        assert!(matches_pattern("Rank*", "Rank"));
+        assert!(matches_pattern("*[*]", "Rank your bigs [1st]"));
+        assert!(matches_pattern("Name", "Name"));
+        assert!(!matches_pattern("Name", "Names"));
+        assert!(!matches_pattern("Rank*", "Other"));
+        assert!(!matches_pattern("a*a", "a"));
+    }
+
+    /// Checks that selectors parse as ranges or patterns.
+    #[test]
+    fn selectors_parse() {
+        assert_eq!(
+            "3-7".parse::<ColumnSelector>().unwrap(),
+            ColumnSelector::Range {
+                start: 3,
+                end: Some(7)
+            }
+        );
+        assert_eq!(
+            "3-".parse::<ColumnSelector>().unwrap(),
+            ColumnSelector::Range {
+                start: 3,
+                end: None
+            }
+        );
+        assert_eq!(
+            "Rank-*".parse::<ColumnSelector>().unwrap(),
+            ColumnSelector::Pattern("Rank-*".into())
+        );
+        assert!("0-2".parse::<ColumnSelector>().is_err());
+        assert!("5-2".parse::<ColumnSelector>().is_err());
+    }
+
+    /// Checks that the default mapping keeps the original behavior.
+    #[test]
+    fn default_mapping() {
+        use Column::*;
+        assert_eq!(
+            columns(&ColumnMapping::default(), "Email,Name,Rank 1,Rank 2").unwrap(),
+            [Metadata, Key, Rank, Rank]
+        );
+    }
+
+    /// Checks a mapping for a form export with extra columns after the ranks.
+    #[test]
+    fn form_mapping() {
+        use Column::*;
+        let mapping = ColumnMapping {
+            key: "Name".into(),
+            role: None,
+            ranks: vec!["Rank*".parse().unwrap()],
+            ignore: vec!["Timestamp".parse().unwrap()],
+            metadata: vec!["Email".parse().unwrap(), "Major".parse().unwrap()],
+        };
+        assert_eq!(
+            columns(
+                &mapping,
+                "Timestamp,Email,Name,Rank 1,Rank 2,Comments,Major,Other"
+            )
+            .unwrap(),
+            [Ignored, Metadata, Key, Rank, Rank, Ignored, Metadata, Ignored]
+        );
+        let mapping = ColumnMapping {
+            metadata: vec!["2-".parse().unwrap()],
+            ..mapping
+        };
+        assert!(columns(&mapping, "Timestamp,Name,Rank 1").is_err());
+        assert!(columns(&mapping, "Timestamp,Id,Rank 1").is_err());
+    }
+
+    /// Checks that the role column of a combined input is neither a rank nor metadata.
+    #[test]
+    fn combined_mapping() {
+        use Column::*;
+        let mapping = ColumnMapping {
+            role: Some("Role".into()),
+            ..Default::default()
+        };
+        assert_eq!(
+            columns(&mapping, "Email,Role,Name,Rank 1,Rank 2").unwrap(),
+            [Metadata, Role, Key, Rank, Rank]
+        );
+        assert_eq!(
+            columns(&mapping, "Email,Name,Role,Rank 1").unwrap(),
+            [Metadata, Key, Role, Rank]
+        );
+        assert!(columns(&mapping, "Email,Name,Rank 1").is_err());
+    }
+
+    /// Checks that role values are matched without regard to case.
+    #[test]
+    fn role_values() {
+        let roles = Roles::default();
+        assert_eq!(roles.kind("big").unwrap(), DynamicKind::Big);
+        assert_eq!(roles.kind("LITTLE").unwrap(), DynamicKind::Little);
+        assert!(roles.kind("").is_err());
+        assert!(roles.kind("Grand Big").is_err());
+    }
+}
@@ -0,0 +1,587 @@
+//! Big-Little Matching
+//!
+//! See the `biglittle` library for more on the matching algorithm.
+
+use anyhow::{anyhow, bail, Result};
+use biglittle::{
+    history::Season,
+    lineage::Lineage,
+    metadata::Metadata,
+    render::{Alignment, Table},
+    stats::Statistics,
+    trace::Trace,
+    Big, Index, Kind, Little, MatchingSet, Names, PreferenceTable,
+};
+use clap::{Parser, Subcommand};
+use history::{read_history, write_history};
+use indexmap::{IndexMap, IndexSet};
+use input::{
+    build, get_index, load_from_reader, reader, shuffle, split_roles, ColumnMapping, Record, Roles,
+};
+use matrix::{load_combined_matrix, load_matrix};
+use options::{Algorithm, Options};
+use result::{print_diff, read_result, write_output};
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+mod history;
+mod input;
+mod matrix;
+mod options;
+mod result;
+
+/// CLI Arguments
+#[derive(Clone, Debug, Parser)]
+#[clap(about, author, version)]
+pub struct Args {
+    /// Subcommand
+    #[clap(subcommand)]
+    pub command: Command,
+}
+
+/// CLI Subcommand
+#[derive(Clone, Debug, Subcommand)]
+pub enum Command {
+    /// Checks the inputs and constraints without running a solver, and reports whether every
+    /// little can be matched
+    Validate(Source),
+
+    /// Runs a solver over the inputs and prints the matching
+    Match(Source),
+
+    /// Prints how often and at which positions each participant was ranked, and who has no
+    /// mutually-acceptable partner
+    Stats(Source),
+
+    /// Compares two result files written with `--output`
+    Diff {
+        /// Old Result File Path
+        old: PathBuf,
+
+        /// New Result File Path
+        new: PathBuf,
+
+        /// Ignores changes in the order of the littles of each big
+        #[clap(long)]
+        ignore_order: bool,
+    },
+
+    /// Runs the matching with the options recorded in a TOML, YAML, or JSON run file
+    Run {
+        /// Run File Path
+        path: PathBuf,
+    },
+
+    /// Prints the family trees grown from the pairings of a history file
+    Lineage {
+        /// History File Path
+        history: PathBuf,
+
+        /// Prints the bigs, littles, ancestors, descendants, and family of this member instead of
+        /// every family
+        #[clap(long, value_name = "NAME")]
+        member: Option<String>,
+
+        /// Writes the family trees as a Graphviz DOT graph to this path
+        #[clap(long, value_name = "PATH")]
+        dot: Option<PathBuf>,
+    },
+}
+
+/// Options Source
+#[derive(Clone, Debug, clap::Args)]
+pub struct Source {
+    /// Reads every option from this TOML, YAML, or JSON run file, which cannot be combined with
+    /// any other option on the command line
+    #[clap(long, value_name = "PATH", conflicts_with = "options")]
+    pub run_file: Option<PathBuf>,
+
+    /// Matching Options
+    #[clap(flatten)]
+    pub options: Options,
+}
+
+impl Source {
+    /// Returns the options from the run file if there is one, or from the command line otherwise.
+    #[inline]
+    pub fn into_options(self) -> Result<Options> {
+        match self.run_file {
+            Some(path) => Options::from_run_file(&path),
+            _ => Ok(self.options),
+        }
+    }
+}
+
+/// Prints the events from `trace` which involve the participant with the given `name`.
+#[inline]
+fn explain(names: &Names, trace: &Trace, name: &str) -> Result<()> {
+    let index = names
+        .find(name)
+        .ok_or_else(|| anyhow!("Unable to find {name} in the name tables."))?;
+    println!("\n{name}:");
+    for event in trace.events_for_dyn(index) {
+        println!("    {}", event.display(names));
+    }
+    Ok(())
+}
+
+/// Loads the big and little records from the inputs described by `options`.
+#[inline]
+fn load_records(options: &Options) -> Result<(IndexMap<String, Record>, IndexMap<String, Record>)> {
+    let mut mapping = ColumnMapping {
+        key: options.key_column.clone(),
+        role: None,
+        ranks: options.rank_columns.clone(),
+        ignore: options.ignore_columns.clone(),
+        metadata: options.metadata_columns.clone(),
+    };
+    let load_side = |path: &PathBuf, matrix| {
+        if matrix {
+            load_matrix(reader(path.clone())?)
+        } else {
+            load_from_reader(reader(path.clone())?, &mapping)
+        }
+    };
+    match (&options.combined, &options.big_input, &options.little_input) {
+        (Some(combined), _, _) if options.combined_matrix => {
+            load_combined_matrix(reader(combined.clone())?)
+        }
+        (Some(combined), _, _) => {
+            mapping.role = Some(options.role_column.clone());
+            let roles = Roles {
+                big: options.big_role.clone(),
+                little: options.little_role.clone(),
+            };
+            split_roles(
+                load_from_reader(reader(combined.clone())?, &mapping)?,
+                &roles,
+            )
+        }
+        (_, Some(bigs), Some(littles)) => Ok((
+            load_side(bigs, options.big_matrix)?,
+            load_side(littles, options.little_matrix)?,
+        )),
+        _ => bail!("Missing input data paths."),
+    }
+}
+
+/// Applies the capacities, the forbidden, forced, and locked pairs, and the co-big units and
+/// little groups from `options` to the `table`.
+#[inline]
+fn apply_constraints(options: &Options, names: &Names, table: &mut PreferenceTable) -> Result<()> {
+    if let Some(capacity) = options.default_capacity {
+        for (big, _) in names.iter::<Big>() {
+            table.set_capacity(big, capacity);
+        }
+    }
+    for capacity in &options.capacity {
+        table.set_capacity(get_index(names, &capacity.big)?, capacity.capacity);
+    }
+    for pair in &options.forbid {
+        table.forbid(
+            get_index(names, &pair.big)?,
+            get_index(names, &pair.little)?,
+        );
+    }
+    for pair in &options.force {
+        table.force(
+            get_index(names, &pair.big)?,
+            get_index(names, &pair.little)?,
+        );
+    }
+    if let Some(path) = &options.lock_file {
+        let mut locked_names = Names::default();
+        let locked = read_result(path.clone(), &mut locked_names)?;
+        for (big, little) in locked.pairs() {
+            table.lock(
+                get_index(names, &locked_names.label(big).to_string())?,
+                get_index(names, &locked_names.label(little).to_string())?,
+            );
+        }
+    }
+    for pair in &options.lock {
+        table.lock(
+            get_index(names, &pair.big)?,
+            get_index(names, &pair.little)?,
+        );
+    }
+    for unit in &options.co_bigs {
+        let first = get_index(names, &unit.0[0])?;
+        for big in &unit.0[1..] {
+            table.pair_co_bigs(first, get_index(names, big)?);
+        }
+    }
+    for group in &options.group {
+        let littles = group
+            .0
+            .iter()
+            .map(|little| get_index(names, little))
+            .collect::<Result<Vec<_>>>()?;
+        table.group_littles(littles);
+    }
+    Ok(())
+}
+
+/// Loads the names, preferences, and metadata described by `options`, applying their
+/// constraints.
+#[inline]
+fn load(options: &Options) -> Result<(Names, PreferenceTable, Metadata)> {
+    let (mut bigs, mut littles) = load_records(options)?;
+    if let Some(mut seed) = options.seed {
+        bigs = shuffle(bigs, &mut seed);
+        littles = shuffle(littles, &mut seed);
+    }
+    let (names, mut preferences, metadata) = build(bigs, littles)?;
+    if let Some(path) = &options.history {
+        let history = read_history(path)?;
+        history.apply(&mut preferences, &names, options.repeat);
+        if options.balance_families {
+            Lineage::from_history(&history).apply(&mut preferences, &names);
+        }
+    }
+    apply_constraints(options, &names, &mut preferences)?;
+    Ok((names, preferences, metadata))
+}
+
+/// Returns the names of the participants at `indices` separated by commas.
+#[inline]
+fn label_list<'i, K>(names: &Names, indices: impl IntoIterator<Item = &'i Index<K>>) -> String
+where
+    K: 'i + Kind,
+{
+    indices
+        .into_iter()
+        .map(|i| names.label(*i).to_string())
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Checks the inputs and constraints described by `options`, printing a summary and a warning
+/// for every suspicious entry.
+#[inline]
+fn validate(options: Options) -> Result<()> {
+    let (bigs, littles) = load_records(&options)?;
+    let mut warnings = Vec::new();
+    for (name, record) in bigs.iter().chain(&littles) {
+        if record.preferences.is_empty() {
+            warnings.push(format!("{name} does not rank anyone."));
+        }
+        let mut seen = IndexSet::new();
+        for preference in &record.preferences {
+            if !seen.insert(preference) {
+                warnings.push(format!("{name} ranks {preference} more than once."));
+            }
+        }
+    }
+    let mut forced = IndexMap::new();
+    for pair in &options.force {
+        if let Some(big) = forced.insert(&pair.little, &pair.big) {
+            warnings.push(format!(
+                "{} is forced to be matched with both {big} and {}.",
+                pair.little, pair.big
+            ));
+        }
+    }
+    let mut locked = IndexMap::new();
+    for pair in &options.lock {
+        if let Some(big) = locked.insert(&pair.little, &pair.big) {
+            warnings.push(format!(
+                "{} is locked to both {big} and {}.",
+                pair.little, pair.big
+            ));
+        }
+    }
+    let (names, preferences, _) = load(&options)?;
+    let mut locked_loads = IndexMap::<_, usize>::new();
+    for (big, _) in preferences.locks() {
+        *locked_loads.entry(big).or_default() += 1;
+    }
+    for (big, load) in locked_loads {
+        if let Some(capacity) = preferences.capacity(big).filter(|c| load > *c) {
+            warnings.push(format!(
+                "{} has {load} locked littles but room for only {capacity}.",
+                names.label(big)
+            ));
+        }
+    }
+    println!(
+        "Loaded {} bigs and {} littles.",
+        names.len::<Big>(),
+        names.len::<Little>()
+    );
+    let feasibility = preferences.feasibility();
+    if feasibility.is_feasible() {
+        println!("Every little can be matched.");
+    } else {
+        println!(
+            "At most {} of {} littles can be matched.",
+            feasibility.maximum_matched(),
+            feasibility.little_count()
+        );
+    }
+    for little in feasibility.unmatchable() {
+        warnings.push(format!(
+            "{} has no mutually-acceptable big with room.",
+            names.label(*little)
+        ));
+    }
+    for violation in feasibility.violations() {
+        warnings.push(format!(
+            "{} only rank {}, who {} room for {} of them.",
+            label_list(&names, violation.littles()),
+            label_list(&names, violation.bigs()),
+            if violation.bigs().len() == 1 {
+                "has"
+            } else {
+                "together have"
+            },
+            violation.capacity()
+        ));
+    }
+    for group in feasibility.lock_conflicts() {
+        warnings.push(format!(
+            "{} must be matched together but are locked to different bigs.",
+            label_list(&names, group)
+        ));
+    }
+    for warning in &warnings {
+        println!("Warning: {warning}");
+    }
+    Ok(())
+}
+
+/// Runs the matching described by `options`.
+#[inline]
+fn run(options: Options) -> Result<()> {
+    let (names, preferences, metadata) = load(&options)?;
+    if let Some(group) = preferences.lock_conflicts().first() {
+        bail!(
+            "{} must be matched together but are locked to different bigs.",
+            label_list(&names, group)
+        );
+    }
+    print_preferences(&names, &preferences, &options);
+    let mut trace = Trace::default();
+    let matching_set = match options.algorithm {
+        Algorithm::Even => preferences.find_even_matching_with_observer(&mut trace),
+        Algorithm::Maximal => preferences.find_maximal_matching_with_observer(&mut trace),
+    };
+    print_matching(&names, &preferences, &matching_set, &options);
+    for pair in &options.force {
+        let big = get_index(&names, &pair.big)?;
+        let little = get_index(&names, &pair.little)?;
+        if !matching_set.pairs().any(|p| p == (big, little)) {
+            eprintln!(
+                "Warning: {} could not be matched with {} within their capacity.",
+                pair.little, pair.big
+            );
+        }
+    }
+    if let Some(name) = &options.explain {
+        explain(&names, &trace, name)?;
+    }
+    if let Some(path) = options.output {
+        write_output(path, &names, &metadata, &matching_set)?;
+    }
+    if let Some(path) = &options.dot {
+        let dot = preferences.dot(&names).matching(&matching_set);
+        fs::write(path, format!("{dot}\n"))
+            .map_err(|err| anyhow!("Unable to write {}: {err}.", path.display()))?;
+    }
+    if let Some(path) = &options.html {
+        let report = matching_set.html(&preferences, &names);
+        fs::write(path, format!("{report}\n"))
+            .map_err(|err| anyhow!("Unable to write {}: {err}.", path.display()))?;
+    }
+    if let Some(path) = &options.history {
+        let mut history = read_history(path)?;
+        let label = options
+            .season
+            .unwrap_or_else(|| format!("Season {}", history.seasons().len() + 1));
+        history.push(Season::new(label, &matching_set, &names));
+        write_history(path, &history)?;
+    }
+    Ok(())
+}
+
+/// Prints the `preferences` in the format of `options`, substituting `names` for indices.
+#[inline]
+fn print_preferences(names: &Names, preferences: &PreferenceTable, options: &Options) {
+    let max_width = options.max_name_width.unwrap_or(usize::MAX);
+    match options.format.table_style() {
+        Some(style) => {
+            for table in [
+                preferences.table::<Big, _>(names),
+                preferences.table::<Little, _>(names),
+            ] {
+                println!("{}\n", table.display(style).max_width(max_width));
+            }
+        }
+        _ => println!("{}\n", preferences.display(names).max_width(max_width)),
+    }
+}
+
+/// Prints the `matching_set` in the format of `options` with the ranks from `preferences`,
+/// substituting `names` for indices.
+#[inline]
+fn print_matching(
+    names: &Names,
+    preferences: &PreferenceTable,
+    matching_set: &MatchingSet,
+    options: &Options,
+) {
+    let max_width = options.max_name_width.unwrap_or(usize::MAX);
+    match options.format.table_style() {
+        Some(style) => {
+            let table = matching_set.table(preferences, names);
+            println!("{}", table.display(style).max_width(max_width));
+        }
+        _ => println!("{}", matching_set.display(names).max_width(max_width)),
+    }
+}
+
+/// Prints a table of the rank counts of each participant of kind `K` from `statistics` in the
+/// format of `options`.
+#[inline]
+fn print_rank_counts<K>(names: &Names, statistics: &Statistics, options: &Options)
+where
+    K: Kind,
+{
+    let ranks = 1..=statistics.longest_ranking::<K>();
+    let mut table = Table::new(
+        [
+            (K::dynamic().to_string(), Alignment::Left),
+            ("Ranked".into(), Alignment::Right),
+        ]
+        .into_iter()
+        .chain(
+            ranks
+                .clone()
+                .map(|rank| (format!("#{rank}"), Alignment::Right)),
+        )
+        .chain([("Mutual".into(), Alignment::Right)]),
+    );
+    for (index, counts) in statistics.iter::<K>() {
+        table.push_row(
+            [names.label(index).to_string(), counts.total().to_string()]
+                .into_iter()
+                .chain(ranks.clone().map(|rank| counts.at(rank).to_string()))
+                .chain([counts.mutual().to_string()]),
+        );
+    }
+    let style = options.format.table_style().unwrap_or_default();
+    let max_width = options.max_name_width.unwrap_or(usize::MAX);
+    println!("{}", table.display(style).max_width(max_width));
+}
+
+/// Prints the names of the participants of kind `K` who have no mutually-acceptable partner.
+#[inline]
+fn print_without_mutual<K>(names: &Names, statistics: &Statistics)
+where
+    K: Kind,
+{
+    let without = statistics
+        .without_mutual::<K>()
+        .map(|i| names.label(i).to_string())
+        .collect::<Vec<_>>();
+    println!(
+        "{}s without a mutually-acceptable {}: {}",
+        K::dynamic(),
+        K::dynamic().opposite().to_string().to_lowercase(),
+        if without.is_empty() {
+            "none".to_string()
+        } else {
+            without.join(", ")
+        }
+    );
+}
+
+/// Prints the preference statistics of the participants described by `options`.
+#[inline]
+fn stats(options: Options) -> Result<()> {
+    let (names, preferences, _) = load(&options)?;
+    let statistics = preferences.statistics();
+    print_rank_counts::<Big>(&names, &statistics, &options);
+    println!();
+    print_rank_counts::<Little>(&names, &statistics, &options);
+    println!();
+    println!("Mutual pairs: {}", statistics.mutual_pairs().len());
+    print_without_mutual::<Big>(&names, &statistics);
+    print_without_mutual::<Little>(&names, &statistics);
+    Ok(())
+}
+
+/// Prints the family trees grown from the pairings of the history file at `path`, or only the
+/// relatives of `member` if given, and writes them as a DOT graph to the `dot` path.
+#[inline]
+fn lineage(path: &Path, member: Option<&str>, dot: Option<PathBuf>) -> Result<()> {
+    let lineage = Lineage::from_history(&read_history(path)?);
+    let list = |members: Vec<&String>| {
+        members
+            .into_iter()
+            .map(String::as_str)
+            .collect::<Vec<_>>()
+            .join(", ")
+    };
+    match member {
+        Some(member) => {
+            if !lineage.contains(member) {
+                bail!("{member} is not part of any family.");
+            }
+            println!("Bigs: {}", list(lineage.bigs(member)));
+            println!("Littles: {}", list(lineage.littles(member)));
+            println!("Ancestors: {}", list(lineage.ancestors(member)));
+            println!("Descendants: {}", list(lineage.descendants(member)));
+            println!("Family: {}", list(lineage.family(member)));
+        }
+        _ => {
+            for (i, family) in lineage.families().into_iter().enumerate() {
+                println!(
+                    "Family {} ({} members): {}",
+                    i + 1,
+                    family.len(),
+                    list(family)
+                );
+            }
+        }
+    }
+    if let Some(path) = dot {
+        fs::write(&path, format!("{}\n", lineage.dot()))
+            .map_err(|err| anyhow!("Unable to write {}: {err}.", path.display()))?;
+    }
+    Ok(())
+}
+
+/// Runs the Big-Little Matching CLI.
+#[inline]
+pub fn main() -> Result<()> {
+    match Args::parse().command {
+        Command::Validate(source) => validate(source.into_options()?),
+        Command::Match(source) => run(source.into_options()?),
+        Command::Stats(source) => stats(source.into_options()?),
+        Command::Diff {
+            old,
+            new,
+            ignore_order,
+        } => {
+            let mut names = Names::default();
+            let old = read_result(old, &mut names)?;
+            let new = read_result(new, &mut names)?;
+            let diff = if ignore_order {
+                old.diff_ignoring_order(&new)
+            } else {
+                old.diff(&new)
+            };
+            print_diff(&names, &old, &new, &diff);
+            Ok(())
+        }
+        Command::Run { path } => run(Options::from_run_file(&path)?),
+        Command::Lineage {
+            history,
+            member,
+            dot,
+        } => lineage(&history, member.as_deref(), dot),
+    }
+}
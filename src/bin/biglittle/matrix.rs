@@ -0,0 +1,270 @@
+//! Preference Matrix Input
+//!
+//! Reads preferences collected as a grid, with one participant in each row, one participant of the
+//! opposite kind in each column, and the rank given by the row to the column in each cell. A
+//! combined grid has bigs as rows and littles as columns with both ranks in each cell written as
+//! `{big rank}/{little rank}`, where either side can be left blank.
+
+use crate::input::Record;
+use anyhow::{ensure, Result};
+use csv::Reader;
+use indexmap::{IndexMap, IndexSet};
+use std::io::Read;
+
+/// Preference Matrix
+struct Matrix {
+    /// Column Names
+    columns: IndexSet<String>,
+
+    /// Row Cells
+    rows: IndexMap<String, Vec<String>>,
+}
+
+impl Matrix {
+    /// Reads a matrix from `reader` where the first header labels the row names and the rest are
+    /// the column names.
+    #[inline]
+    fn read<R>(mut reader: Reader<R>) -> Result<Self>
+    where
+        R: Read,
+    {
+        let mut columns = IndexSet::new();
+        for column in reader.headers()?.iter().skip(1) {
+            ensure!(
+                !column.is_empty(),
+                "Missing column name in the preference matrix."
+            );
+            ensure!(
+                columns.insert(column.to_string()),
+                "Duplicate column in the preference matrix: {column}."
+            );
+        }
+        let mut rows = IndexMap::new();
+        for record in reader.records() {
+            let record = record?;
+            let mut cells = record.iter();
+            let row = cells.next().unwrap_or_default();
+            ensure!(
+                !row.is_empty(),
+                "Missing row name in the preference matrix."
+            );
+            let cells = cells.map(str::to_string).collect::<Vec<_>>();
+            ensure!(
+                cells.len() <= columns.len(),
+                "Row {row} has more cells than the preference matrix has columns."
+            );
+            ensure!(
+                rows.insert(row.to_string(), cells).is_none(),
+                "Duplicate row in the preference matrix: {row}."
+            );
+        }
+        Ok(Self { columns, rows })
+    }
+
+    /// Returns an iterator over the `(row, column, cell)` entries of the matrix which are not
+    /// blank.
+    #[inline]
+    fn cells(&self) -> impl Iterator<Item = (&str, &str, &str)> {
+        self.rows.iter().flat_map(move |(row, cells)| {
+            self.columns
+                .iter()
+                .zip(cells)
+                .filter(|(_, cell)| !cell.is_empty())
+                .map(move |(column, cell)| (row.as_str(), column.as_str(), cell.as_str()))
+        })
+    }
+}
+
+/// Rankings
+///
+/// The ranks given by each participant, keyed by their name.
+type Rankings = IndexMap<String, Vec<(usize, String)>>;
+
+/// Returns empty rankings for every participant in `names`.
+#[inline]
+fn rankings<'n, I>(names: I) -> Rankings
+where
+    I: IntoIterator<Item = &'n String>,
+{
+    names.into_iter().map(|n| (n.clone(), Vec::new())).collect()
+}
+
+/// Parses the rank `cell` given by `owner` to `ranked` and adds it to the `rankings`, recording
+/// any problem in `problems`.
+#[inline]
+fn insert_rank(
+    rankings: &mut Rankings,
+    owner: &str,
+    ranked: &str,
+    cell: &str,
+    problems: &mut Vec<String>,
+) {
+    match cell.parse::<usize>() {
+        Ok(rank) if rank > 0 => rankings[owner].push((rank, ranked.to_string())),
+        _ => problems.push(format!("{owner} gives {ranked} an invalid rank: `{cell}`.")),
+    }
+}
+
+/// Orders the `rankings` into records, recording duplicate and missing ranks in `problems`.
+#[inline]
+fn into_records(rankings: Rankings, problems: &mut Vec<String>) -> IndexMap<String, Record> {
+    rankings
+        .into_iter()
+        .map(|(owner, mut ranks)| {
+            ranks.sort();
+            let mut expected = 1;
+            for (i, (rank, ranked)) in ranks.iter().enumerate() {
+                if i > 0 && ranks[i - 1].0 == *rank {
+                    problems.push(format!(
+                        "{owner} gives rank {rank} to both {} and {ranked}.",
+                        ranks[i - 1].1
+                    ));
+                }
+                while expected < *rank {
+                    problems.push(format!("{owner} is missing rank {expected}."));
+                    expected += 1;
+                }
+                expected = rank + 1;
+            }
+            let record = Record {
+                preferences: ranks.into_iter().map(|(_, ranked)| ranked).collect(),
+                ..Default::default()
+            };
+            (owner, record)
+        })
+        .collect()
+}
+
+/// Checks that no `problems` were found in the matrix.
+#[inline]
+fn check(problems: Vec<String>) -> Result<()> {
+    ensure!(
+        problems.is_empty(),
+        "Invalid preference matrix:\n    {}",
+        problems.join("\n    ")
+    );
+    Ok(())
+}
+
+/// Loads the records of the row participants from a one-sided preference matrix in `reader`.
+#[inline]
+pub fn load_matrix<R>(reader: Reader<R>) -> Result<IndexMap<String, Record>>
+where
+    R: Read,
+{
+    let matrix = Matrix::read(reader)?;
+    let mut problems = Vec::new();
+    let mut ranks = rankings(matrix.rows.keys());
+    for (row, column, cell) in matrix.cells() {
+        insert_rank(&mut ranks, row, column, cell, &mut problems);
+    }
+    let records = into_records(ranks, &mut problems);
+    check(problems)?;
+    Ok(records)
+}
+
+/// Loads the big and little records from a combined preference matrix in `reader` with bigs as
+/// rows and littles as columns.
+#[inline]
+pub fn load_combined_matrix<R>(
+    reader: Reader<R>,
+) -> Result<(IndexMap<String, Record>, IndexMap<String, Record>)>
+where
+    R: Read,
+{
+    let matrix = Matrix::read(reader)?;
+    let mut problems = Vec::new();
+    let mut big_ranks = rankings(matrix.rows.keys());
+    let mut little_ranks = rankings(&matrix.columns);
+    for (big, little, cell) in matrix.cells() {
+        match cell.split_once('/') {
+            Some((big_rank, little_rank)) => {
+                let (big_rank, little_rank) = (big_rank.trim(), little_rank.trim());
+                if !big_rank.is_empty() {
+                    insert_rank(&mut big_ranks, big, little, big_rank, &mut problems);
+                }
+                if !little_rank.is_empty() {
+                    insert_rank(&mut little_ranks, little, big, little_rank, &mut problems);
+                }
+            }
+            _ => problems.push(format!(
+                "The cell for {big} and {little} is not of the form \
+                 `{{big rank}}/{{little rank}}`: `{cell}`."
+            )),
+        }
+    }
+    let bigs = into_records(big_ranks, &mut problems);
+    let littles = into_records(little_ranks, &mut problems);
+    check(problems)?;
+    Ok((bigs, littles))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use csv::{ReaderBuilder, Trim};
+
+    /// Returns a CSV reader over `input` with the same settings as the file readers.
+    fn reader(input: &str) -> Reader<&[u8]> {
+        ReaderBuilder::new()
+            .flexible(true)
+            .trim(Trim::All)
+            .from_reader(input.as_bytes())
+    }
+
+    /// Returns the preferences of every record in `records`.
+    fn preferences(records: &IndexMap<String, Record>) -> Vec<(&str, Vec<&str>)> {
+        records
+            .iter()
+            .map(|(name, record)| {
+                let preferences = record.preferences.iter().map(String::as_str).collect();
+                (name.as_str(), preferences)
+            })
+            .collect()
+    }
+
+    /// Checks that the ranks in a one-sided matrix are turned into ordered preferences.
+    #[test]
+    fn one_sided_matrix() {
+        let records =
+            load_matrix(reader("Big,Dan,Erin,Finn\nAlice,2,1,\nBob,,,1\nCara,,,")).unwrap();
+        assert_eq!(
+            preferences(&records),
+            [
+                ("Alice", vec!["Erin", "Dan"]),
+                ("Bob", vec!["Finn"]),
+                ("Cara", vec![])
+            ]
+        );
+    }
+
+    /// Checks that a combined matrix is split into big and little preferences.
+    #[test]
+    fn combined_matrix() {
+        let (bigs, littles) =
+            load_combined_matrix(reader("Big,Dan,Erin\nAlice,1/2,2/1\nBob,/1,1/")).unwrap();
+        assert_eq!(
+            preferences(&bigs),
+            [("Alice", vec!["Dan", "Erin"]), ("Bob", vec!["Erin"])]
+        );
+        assert_eq!(
+            preferences(&littles),
+            [("Dan", vec!["Bob", "Alice"]), ("Erin", vec!["Alice"])]
+        );
+    }
+
+    /// Checks that every duplicate, missing, and invalid rank is reported.
+    #[test]
+    fn rank_problems() {
+        let error = load_matrix(reader("Big,Dan,Erin,Finn\nAlice,1,1,\nBob,3,,x"))
+            .unwrap_err()
+            .to_string();
+        assert!(error.contains("Alice gives rank 1 to both Dan and Erin."));
+        assert!(error.contains("Bob is missing rank 1."));
+        assert!(error.contains("Bob is missing rank 2."));
+        assert!(error.contains("Bob gives Finn an invalid rank: `x`."));
+        assert!(load_combined_matrix(reader("Big,Dan\nAlice,1")).is_err());
+        assert!(load_matrix(reader("Big,Dan,Dan\nAlice,1,2")).is_err());
+        assert!(load_matrix(reader("Big,Dan\nAlice,1\nAlice,1")).is_err());
+    }
+}
@@ -0,0 +1,588 @@
+//! Matching Options
+//!
+//! Every option can be given on the command line or recorded in a TOML, YAML, or JSON run file
+//! with the same names as the long flags, so that a matching can be reproduced from one file.
+
+use crate::{history::repeat_policy, input::ColumnSelector};
+use anyhow::{anyhow, bail, Result};
+use biglittle::{history::RepeatPolicy, render::TableStyle};
+use clap::{ArgGroup, Args};
+use serde::{Deserialize, Deserializer};
+use std::{
+    collections::BTreeMap,
+    ffi::OsStr,
+    fs,
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+
+/// Matching Algorithm
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq)]
+#[serde(rename_all = "kebab-case")]
+pub enum Algorithm {
+    /// Evenly-Distributed Matching
+    Even,
+
+    /// Maximal Matching
+    Maximal,
+}
+
+impl FromStr for Algorithm {
+    type Err = anyhow::Error;
+
+    #[inline]
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "even" => Ok(Self::Even),
+            "maximal" => Ok(Self::Maximal),
+            _ => bail!("Unknown algorithm `{s}`, expected `even` or `maximal`."),
+        }
+    }
+}
+
+/// Output Format
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq)]
+#[serde(rename_all = "kebab-case")]
+pub enum Format {
+    /// Struct-Like Listing
+    Struct,
+
+    /// Aligned ASCII Tables
+    Ascii,
+
+    /// GitHub Markdown Tables
+    Markdown,
+}
+
+impl Format {
+    /// Returns the table style of `self`, or `None` for the struct-like listing.
+    #[inline]
+    pub fn table_style(self) -> Option<TableStyle> {
+        match self {
+            Self::Struct => None,
+            Self::Ascii => Some(TableStyle::Ascii),
+            Self::Markdown => Some(TableStyle::Markdown),
+        }
+    }
+}
+
+impl FromStr for Format {
+    type Err = anyhow::Error;
+
+    #[inline]
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "struct" => Ok(Self::Struct),
+            "ascii" => Ok(Self::Ascii),
+            "markdown" => Ok(Self::Markdown),
+            _ => bail!("Unknown format `{s}`, expected `struct`, `ascii`, or `markdown`."),
+        }
+    }
+}
+
+/// Participant Pair
+///
+/// A big and a little, written as `{big}:{little}`.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq)]
+#[serde(try_from = "String")]
+pub struct Pair {
+    /// Big Name
+    pub big: String,
+
+    /// Little Name
+    pub little: String,
+}
+
+impl FromStr for Pair {
+    type Err = anyhow::Error;
+
+    #[inline]
+    fn from_str(s: &str) -> Result<Self> {
+        match s.split_once(':') {
+            Some((big, little)) if !big.trim().is_empty() && !little.trim().is_empty() => {
+                Ok(Self {
+                    big: big.trim().to_string(),
+                    little: little.trim().to_string(),
+                })
+            }
+            _ => bail!("Pair `{s}` is not of the form `{{big}}:{{little}}`."),
+        }
+    }
+}
+
+impl TryFrom<String> for Pair {
+    type Error = anyhow::Error;
+
+    #[inline]
+    fn try_from(s: String) -> Result<Self> {
+        s.parse()
+    }
+}
+
+/// Name List
+///
+/// Two or more participants of the same kind, written as `{name},{name}`.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq)]
+#[serde(try_from = "String")]
+pub struct NameList(pub Vec<String>);
+
+impl FromStr for NameList {
+    type Err = anyhow::Error;
+
+    #[inline]
+    fn from_str(s: &str) -> Result<Self> {
+        let names = s
+            .split(',')
+            .map(|name| name.trim().to_string())
+            .collect::<Vec<_>>();
+        if names.len() < 2 || names.iter().any(String::is_empty) {
+            bail!("Name list `{s}` is not of the form `{{name}},{{name}}`.");
+        }
+        Ok(Self(names))
+    }
+}
+
+impl TryFrom<String> for NameList {
+    type Error = anyhow::Error;
+
+    #[inline]
+    fn try_from(s: String) -> Result<Self> {
+        s.parse()
+    }
+}
+
+/// Big Capacity
+///
+/// The largest number of littles a big can be matched with, written as `{big}={capacity}`.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Capacity {
+    /// Big Name
+    pub big: String,
+
+    /// Capacity
+    pub capacity: usize,
+}
+
+impl FromStr for Capacity {
+    type Err = anyhow::Error;
+
+    #[inline]
+    fn from_str(s: &str) -> Result<Self> {
+        let (big, capacity) = s
+            .rsplit_once('=')
+            .ok_or_else(|| anyhow!("Capacity `{s}` is not of the form `{{big}}={{capacity}}`."))?;
+        Ok(Self {
+            big: big.trim().to_string(),
+            capacity: capacity.trim().parse()?,
+        })
+    }
+}
+
+/// Deserializes capacities from a table of big names and their capacities.
+#[inline]
+fn capacities<'de, D>(deserializer: D) -> Result<Vec<Capacity>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    Ok(BTreeMap::<String, usize>::deserialize(deserializer)?
+        .into_iter()
+        .map(|(big, capacity)| Capacity { big, capacity })
+        .collect())
+}
+
+/// Matching Options
+#[derive(Args, Clone, Debug, Deserialize)]
+#[clap(group(ArgGroup::new("options").multiple(true)))]
+#[serde(default, deny_unknown_fields, rename_all = "kebab-case")]
+pub struct Options {
+    /// Big Input Data Path
+    #[clap(
+        group = "options",
+        required_unless_present_any = &["combined", "run-file"],
+        requires = "little-input"
+    )]
+    pub big_input: Option<PathBuf>,
+
+    /// Little Input Data Path
+    #[clap(group = "options")]
+    pub little_input: Option<PathBuf>,
+
+    /// Combined Input Data Path, with both bigs and littles marked by a role column
+    #[clap(
+        long,
+        group = "options",
+        value_name = "PATH",
+        conflicts_with_all = &["big-input", "little-input"]
+    )]
+    pub combined: Option<PathBuf>,
+
+    /// Reads the big input as a preference matrix with bigs as rows, littles as columns, and
+    /// ranks in the cells
+    #[clap(long, group = "options")]
+    pub big_matrix: bool,
+
+    /// Reads the little input as a preference matrix with littles as rows, bigs as columns, and
+    /// ranks in the cells
+    #[clap(long, group = "options")]
+    pub little_matrix: bool,
+
+    /// Reads the combined input as a preference matrix with bigs as rows, littles as columns, and
+    /// cells of the form `{big rank}/{little rank}`
+    #[clap(long, group = "options", requires = "combined")]
+    pub combined_matrix: bool,
+
+    /// Header of the column which marks each participant of a combined input as a big or a
+    /// little
+    #[clap(long, group = "options", default_value = "Role", value_name = "HEADER")]
+    pub role_column: String,
+
+    /// Role value which marks a big in a combined input
+    #[clap(long, group = "options", default_value = "Big", value_name = "VALUE")]
+    pub big_role: String,
+
+    /// Role value which marks a little in a combined input
+    #[clap(
+        long,
+        group = "options",
+        default_value = "Little",
+        value_name = "VALUE"
+    )]
+    pub little_role: String,
+
+    /// Header of the column which identifies each participant, like a name or a student ID
+    #[clap(long, group = "options", default_value = "Name", value_name = "HEADER")]
+    pub key_column: String,
+
+    /// Columns which hold the ranks, as header patterns like `Rank*` or one-based column ranges
+    /// like `3-7` [default: every column after the key column]
+    #[clap(
+        long,
+        group = "options",
+        value_name = "COLUMNS",
+        multiple_occurrences = true
+    )]
+    pub rank_columns: Vec<ColumnSelector>,
+
+    /// Columns to ignore, as header patterns or column ranges
+    #[clap(
+        long,
+        group = "options",
+        value_name = "COLUMNS",
+        multiple_occurrences = true
+    )]
+    pub ignore_columns: Vec<ColumnSelector>,
+
+    /// Columns to keep as metadata, as header patterns or column ranges [default: every column
+    /// before the key column]
+    #[clap(
+        long,
+        group = "options",
+        value_name = "COLUMNS",
+        multiple_occurrences = true
+    )]
+    pub metadata_columns: Vec<ColumnSelector>,
+
+    /// Matching algorithm, either `even` or `maximal`
+    #[clap(
+        long,
+        group = "options",
+        default_value = "even",
+        value_name = "ALGORITHM"
+    )]
+    pub algorithm: Algorithm,
+
+    /// Largest number of littles for every big without their own capacity [default: unlimited]
+    #[clap(long, group = "options", value_name = "CAPACITY")]
+    pub default_capacity: Option<usize>,
+
+    /// Largest number of littles for one big, as `{big}={capacity}`
+    #[clap(
+        long,
+        group = "options",
+        value_name = "CAPACITY",
+        multiple_occurrences = true
+    )]
+    #[serde(deserialize_with = "capacities")]
+    pub capacity: Vec<Capacity>,
+
+    /// Pair which must not be matched, as `{big}:{little}`
+    #[clap(
+        long,
+        group = "options",
+        value_name = "PAIR",
+        multiple_occurrences = true
+    )]
+    pub forbid: Vec<Pair>,
+
+    /// Pair which must be matched, as `{big}:{little}`
+    #[clap(
+        long,
+        group = "options",
+        value_name = "PAIR",
+        multiple_occurrences = true
+    )]
+    pub force: Vec<Pair>,
+
+    /// Pair which is already confirmed and must not move, as `{big}:{little}`
+    #[clap(
+        long,
+        group = "options",
+        value_name = "PAIR",
+        multiple_occurrences = true
+    )]
+    pub lock: Vec<Pair>,
+
+    /// Bigs who share their littles as one co-big unit with their capacities combined, as
+    /// `{big},{big}`
+    #[clap(
+        long,
+        group = "options",
+        value_name = "BIGS",
+        multiple_occurrences = true
+    )]
+    pub co_bigs: Vec<NameList>,
+
+    /// Littles who must be matched with the same big or not at all, as `{little},{little}`
+    #[clap(
+        long,
+        group = "options",
+        value_name = "LITTLES",
+        multiple_occurrences = true
+    )]
+    pub group: Vec<NameList>,
+
+    /// Locks every pair of a result file written with `--output`, like the confirmed part of a
+    /// previous matching
+    #[clap(long, group = "options", value_name = "PATH")]
+    pub lock_file: Option<PathBuf>,
+
+    /// History file of past seasons, which is consulted to avoid repeat pairings and gets the
+    /// new matching appended as the newest season
+    #[clap(long, group = "options", value_name = "PATH")]
+    pub history: Option<PathBuf>,
+
+    /// What to do with pairings from past seasons, either `penalize` to make them a last resort
+    /// or `forbid` to rule them out
+    #[clap(
+        long,
+        group = "options",
+        default_value = "penalize",
+        value_name = "POLICY",
+        parse(try_from_str = repeat_policy)
+    )]
+    pub repeat: RepeatPolicy,
+
+    /// Evens out the sizes of the families grown from the history file instead of the number of
+    /// littles of each big
+    #[clap(long, group = "options", requires = "history")]
+    pub balance_families: bool,
+
+    /// Label of the season appended to the history file [default: `Season {number}`]
+    #[clap(long, group = "options", value_name = "LABEL")]
+    pub season: Option<String>,
+
+    /// Shuffles the order of the participants with this seed, which changes how ties between
+    /// equally good matchings are broken
+    #[clap(long, group = "options", value_name = "SEED")]
+    pub seed: Option<u64>,
+
+    /// Explains how the participant with this name ended up with their match
+    #[clap(long, group = "options", value_name = "NAME")]
+    pub explain: Option<String>,
+
+    /// Format of the printed preferences and matching, either `struct`, `ascii` for aligned tables
+    /// with the rank of each pair, or `markdown` for GitHub Markdown tables
+    #[clap(
+        long,
+        group = "options",
+        default_value = "struct",
+        value_name = "FORMAT"
+    )]
+    pub format: Format,
+
+    /// Truncates printed names wider than this many columns, ending them with an ellipsis
+    #[clap(long, group = "options", value_name = "WIDTH")]
+    pub max_name_width: Option<usize>,
+
+    /// Writes the matching as a CSV file to this path, or as JSON if it ends in `.json`,
+    /// including the metadata of every participant
+    #[clap(long, group = "options", value_name = "PATH")]
+    pub output: Option<PathBuf>,
+
+    /// Writes the preferences as a Graphviz DOT graph to this path, with the matched pairs
+    /// highlighted
+    #[clap(long, group = "options", value_name = "PATH")]
+    pub dot: Option<PathBuf>,
+
+    /// Writes a self-contained HTML report of the matching to this path, with the rank each
+    /// participant received, statistics, blocking pairs, and the unmatched participants
+    #[clap(long, group = "options", value_name = "PATH")]
+    pub html: Option<PathBuf>,
+}
+
+impl Options {
+    /// Loads the options from the TOML, YAML, or JSON run file at `path`, resolving relative paths
+    /// in the run file against its directory.
+    #[inline]
+    pub fn from_run_file(path: &Path) -> Result<Self> {
+        let contents = fs::read_to_string(path)
+            .map_err(|err| anyhow!("Unable to read run file {}: {err}.", path.display()))?;
+        let mut options: Self = match path.extension().and_then(OsStr::to_str) {
+            Some("toml") => toml::from_str(&contents)?,
+            Some("yaml" | "yml") => serde_yaml::from_str(&contents)?,
+            Some("json") => serde_json::from_str(&contents)?,
+            Some(ext) => bail!("Unrecognized run file format: {ext}."),
+            _ => bail!("Unable to parse run file path: {}.", path.display()),
+        };
+        if let Some(directory) = path.parent() {
+            for path in [
+                &mut options.big_input,
+                &mut options.little_input,
+                &mut options.combined,
+                &mut options.lock_file,
+                &mut options.history,
+                &mut options.output,
+                &mut options.dot,
+                &mut options.html,
+            ]
+            .into_iter()
+            .flatten()
+            {
+                *path = directory.join(&*path);
+            }
+        }
+        Ok(options)
+    }
+}
+
+impl Default for Options {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            big_input: None,
+            little_input: None,
+            combined: None,
+            big_matrix: false,
+            little_matrix: false,
+            combined_matrix: false,
+            role_column: "Role".to_string(),
+            big_role: "Big".to_string(),
+            little_role: "Little".to_string(),
+            key_column: "Name".to_string(),
+            rank_columns: Vec::new(),
+            ignore_columns: Vec::new(),
+            metadata_columns: Vec::new(),
+            algorithm: Algorithm::Even,
+            default_capacity: None,
+            capacity: Vec::new(),
+            forbid: Vec::new(),
+            force: Vec::new(),
+            lock: Vec::new(),
+            co_bigs: Vec::new(),
+            group: Vec::new(),
+            lock_file: None,
+            history: None,
+            repeat: RepeatPolicy::Penalize,
+            balance_families: false,
+            season: None,
+            seed: None,
+            explain: None,
+            format: Format::Struct,
+            max_name_width: None,
+            output: None,
+            dot: None,
+            html: None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    /// Checks that pairs and capacities parse from their command-line forms.
+    #[test]
+    fn values_parse() {
+        let pair = "Alice : Dan".parse::<Pair>().unwrap();
+        assert_eq!((pair.big.as_str(), pair.little.as_str()), ("Alice", "Dan"));
+        assert!("Alice".parse::<Pair>().is_err());
+        assert!(":Dan".parse::<Pair>().is_err());
+        let capacity = "Alice=2".parse::<Capacity>().unwrap();
+        assert_eq!((capacity.big.as_str(), capacity.capacity), ("Alice", 2));
+        assert!("Alice=two".parse::<Capacity>().is_err());
+    }
+
+    /// Checks that the run file formats agree with each other.
+    #[test]
+    fn run_file_formats() {
+        let from_toml: Options = toml::from_str(
+            r#"
+            big-input = "bigs.csv"
+            little-input = "littles.csv"
+            rank-columns = ["Rank*"]
+            algorithm = "maximal"
+            forbid = ["Alice:Dan"]
+            seed = 7
+
+            [capacity]
+            Alice = 2
+            "#,
+        )
+        .unwrap();
+        let from_yaml: Options = serde_yaml::from_str(
+            "
+            big-input: bigs.csv
+            little-input: littles.csv
+            rank-columns: [Rank*]
+            algorithm: maximal
+            forbid: ['Alice:Dan']
+            seed: 7
+            capacity:
+              Alice: 2
+            ",
+        )
+        .unwrap();
+        let from_json: Options = serde_json::from_str(
+            r#"{
+                "big-input": "bigs.csv",
+                "little-input": "littles.csv",
+                "rank-columns": ["Rank*"],
+                "algorithm": "maximal",
+                "forbid": ["Alice:Dan"],
+                "seed": 7,
+                "capacity": { "Alice": 2 }
+            }"#,
+        )
+        .unwrap();
+        for options in [&from_toml, &from_yaml, &from_json] {
+            assert_eq!(options.big_input, Some("bigs.csv".into()));
+            assert_eq!(options.rank_columns, ["Rank*".parse().unwrap()]);
+            assert_eq!(options.algorithm, Algorithm::Maximal);
+            assert_eq!(options.forbid, ["Alice:Dan".parse().unwrap()]);
+            assert_eq!(options.capacity, ["Alice=2".parse().unwrap()]);
+            assert_eq!(options.seed, Some(7));
+            assert_eq!(options.key_column, "Name");
+        }
+        assert!(toml::from_str::<Options>("unknown = 1").is_err());
+    }
+
+    /// Checks that a run file cannot be combined with other options on the command line.
+    #[test]
+    fn run_file_conflicts() {
+        use crate::Args;
+        use clap::Parser;
+        assert!(Args::try_parse_from(["biglittle", "match", "--run-file", "run.toml"]).is_ok());
+        assert!(Args::try_parse_from(["biglittle", "run", "run.toml"]).is_ok());
+        assert!(Args::try_parse_from(["biglittle", "run", "run.toml", "--seed", "7"]).is_err());
+        for extra in [
+            &["bigs.csv", "littles.csv"][..],
+            &["--seed", "7"],
+            &["--format", "ascii"],
+            &["--output", "out.csv"],
+        ] {
+            let mut args = vec!["biglittle", "match", "--run-file", "run.toml"];
+            args.extend(extra);
+            assert!(Args::try_parse_from(args).is_err(), "{extra:?}");
+        }
+    }
+}
@@ -0,0 +1,381 @@
+//! Matching Result Files
+//!
+//! Matchings are saved as CSV files with a `Big` and a `Little` column for each pair, followed by
+//! the metadata of each participant. Unmatched participants have a row with the other column left
+//! empty. If any bigs share their littles as a co-big unit, a `Co-Bigs` column lists the other
+//! members of the unit of each big, separated by commas. Saved with a `.json` extension instead,
+//! the same rows are written as a JSON array of objects with the name and metadata of the `big`
+//! and the `little`, where a missing participant is `null`, and the names of the `co-bigs` if
+//! there are any. Both formats can be read back to compare two matchings.
+
+use crate::input::{insert_name, reader};
+use anyhow::{anyhow, ensure, Result};
+use biglittle::{
+    diff::MatchingDiff, metadata::Metadata, Big, BigIndex, Index, Kind, Little, LittleIndex,
+    MatchingSet, Names,
+};
+use csv::Writer;
+use indexmap::IndexSet;
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::BTreeMap,
+    ffi::OsStr,
+    fs,
+    path::{Path, PathBuf},
+};
+
+/// Result Participant
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+struct Participant {
+    /// Name
+    name: String,
+
+    /// Metadata
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    metadata: BTreeMap<String, String>,
+}
+
+impl Participant {
+    /// Builds the [`Participant`] at `index` with their name and metadata.
+    #[inline]
+    fn new<K>(names: &Names, metadata: &Metadata, index: Index<K>) -> Self
+    where
+        K: Kind,
+    {
+        Self {
+            name: names.label(index).to_string(),
+            metadata: metadata
+                .fields::<K>()
+                .iter()
+                .filter_map(|f| Some((f.clone(), metadata.get(index, f)?.to_string())))
+                .collect(),
+        }
+    }
+}
+
+/// Result Row
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+struct Row {
+    /// Big
+    big: Option<Participant>,
+
+    /// Co-Big Names
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    co_bigs: Vec<String>,
+
+    /// Little
+    little: Option<Participant>,
+}
+
+/// Returns `true` if `path` has a `.json` extension.
+#[inline]
+fn is_json(path: &Path) -> bool {
+    path.extension().and_then(OsStr::to_str) == Some("json")
+}
+
+/// Returns one row for each pair and each unmatched participant of the `matching_set`.
+#[inline]
+fn rows(
+    matching_set: &MatchingSet,
+) -> impl Iterator<Item = (Option<BigIndex>, Option<LittleIndex>)> + '_ {
+    matching_set
+        .pairs()
+        .map(|(big, little)| (Some(big), Some(little)))
+        .chain(
+            matching_set
+                .unmatched_bigs()
+                .iter()
+                .map(|b| (Some(*b), None)),
+        )
+        .chain(
+            matching_set
+                .unmatched_littles()
+                .iter()
+                .map(|l| (None, Some(*l))),
+        )
+}
+
+/// Returns the names of the co-bigs of `big` in the `matching_set`, or nothing if there is no big.
+#[inline]
+fn co_big_names(names: &Names, matching_set: &MatchingSet, big: Option<BigIndex>) -> Vec<String> {
+    big.into_iter()
+        .flat_map(|b| matching_set.co_bigs(b))
+        .map(|b| names.label(b).to_string())
+        .collect()
+}
+
+/// Returns the name and metadata columns of the participant at `index`, or empty columns if
+/// there is no participant.
+#[inline]
+fn output_columns<K>(names: &Names, metadata: &Metadata, index: Option<Index<K>>) -> Vec<String>
+where
+    K: Kind,
+{
+    let mut columns = vec![index
+        .map(|i| names.label(i).to_string())
+        .unwrap_or_default()];
+    for field in metadata.fields::<K>() {
+        columns.push(
+            index
+                .and_then(|i| metadata.get(i, field))
+                .unwrap_or_default()
+                .to_string(),
+        );
+    }
+    columns
+}
+
+/// Writes the `matching_set` to `path` with one row for each pair and each unmatched participant,
+/// followed by their metadata, as JSON if `path` ends in `.json` and as CSV otherwise.
+#[inline]
+pub fn write_output(
+    path: PathBuf,
+    names: &Names,
+    metadata: &Metadata,
+    matching_set: &MatchingSet,
+) -> Result<()> {
+    if is_json(&path) {
+        let rows = rows(matching_set)
+            .map(|(big, little)| Row {
+                big: big.map(|b| Participant::new(names, metadata, b)),
+                co_bigs: co_big_names(names, matching_set, big),
+                little: little.map(|l| Participant::new(names, metadata, l)),
+            })
+            .collect::<Vec<_>>();
+        return fs::write(&path, serde_json::to_string_pretty(&rows)? + "\n")
+            .map_err(|err| anyhow!("Unable to write {}: {err}.", path.display()));
+    }
+    let has_co_bigs = matching_set.matches().iter().any(|m| !m.co_bigs.is_empty());
+    let mut writer = Writer::from_path(path)?;
+    let mut header = vec!["Big".to_string()];
+    header.extend(metadata.fields::<Big>().iter().map(|f| format!("Big {f}")));
+    if has_co_bigs {
+        header.push("Co-Bigs".to_string());
+    }
+    header.push("Little".to_string());
+    header.extend(
+        metadata
+            .fields::<Little>()
+            .iter()
+            .map(|f| format!("Little {f}")),
+    );
+    writer.write_record(header)?;
+    for (big, little) in rows(matching_set) {
+        let mut row = output_columns(names, metadata, big);
+        if has_co_bigs {
+            row.push(co_big_names(names, matching_set, big).join(", "));
+        }
+        row.extend(output_columns(names, metadata, little));
+        writer.write_record(row)?;
+    }
+    writer.flush()?;
+    Ok(())
+}
+
+/// Returns the big, co-big, and little names of every row of the result file at `path`, with an
+/// empty name for a missing participant.
+#[inline]
+fn read_rows(path: PathBuf) -> Result<Vec<(String, Vec<String>, String)>> {
+    if is_json(&path) {
+        let contents = fs::read_to_string(&path)
+            .map_err(|err| anyhow!("Unable to read {}: {err}.", path.display()))?;
+        let name = |p: Option<Participant>| p.map(|p| p.name).unwrap_or_default();
+        return Ok(serde_json::from_str::<Vec<Row>>(&contents)?
+            .into_iter()
+            .map(|row| (name(row.big), row.co_bigs, name(row.little)))
+            .collect());
+    }
+    let mut reader = reader(path)?;
+    let headers = reader.headers()?.clone();
+    let position = |header| {
+        headers
+            .iter()
+            .position(|h| h == header)
+            .ok_or_else(|| anyhow!("Missing `{header}` header."))
+    };
+    let (big_column, little_column) = (position("Big")?, position("Little")?);
+    let co_bigs_column = position("Co-Bigs").ok();
+    let mut rows = Vec::new();
+    for record in reader.records() {
+        let record = record?;
+        let co_bigs = co_bigs_column
+            .and_then(|c| record.get(c))
+            .unwrap_or_default()
+            .split(',')
+            .map(str::trim)
+            .filter(|name| !name.is_empty())
+            .map(String::from)
+            .collect();
+        rows.push((
+            record.get(big_column).unwrap_or_default().to_string(),
+            co_bigs,
+            record.get(little_column).unwrap_or_default().to_string(),
+        ));
+    }
+    Ok(rows)
+}
+
+/// Reads the CSV or JSON result file at `path`, adding every participant to `names`.
+#[inline]
+pub fn read_result(path: PathBuf, names: &mut Names) -> Result<MatchingSet> {
+    let mut pairs = Vec::new();
+    let mut matched_littles = IndexSet::new();
+    let mut unmatched_bigs = Vec::new();
+    let mut unmatched_littles = Vec::new();
+    let mut co_bigs = Vec::new();
+    for (big, co_big_names, little) in read_rows(path)? {
+        if !big.is_empty() && !co_big_names.is_empty() {
+            let big = insert_name::<Big>(names, &big)?;
+            for co_big in co_big_names {
+                co_bigs.push((big, insert_name::<Big>(names, &co_big)?));
+            }
+        }
+        match (big.is_empty(), little.is_empty()) {
+            (false, false) => {
+                let little = insert_name::<Little>(names, &little)?;
+                ensure!(
+                    matched_littles.insert(little),
+                    "Little {} is matched more than once.",
+                    names.label(little)
+                );
+                pairs.push((insert_name::<Big>(names, &big)?, little));
+            }
+            (false, true) => unmatched_bigs.push(insert_name::<Big>(names, &big)?),
+            (true, false) => unmatched_littles.push(insert_name::<Little>(names, &little)?),
+            _ => {}
+        }
+    }
+    let mut matching_set = MatchingSet::from_parts(pairs, unmatched_bigs, unmatched_littles);
+    for (big, co_big) in co_bigs {
+        let mut unit = matching_set.co_bigs(big);
+        unit.insert(co_big);
+        matching_set.set_co_bigs(big, unit);
+    }
+    Ok(matching_set)
+}
+
+/// Prints a heading followed by each of the `lines`, or nothing if there are no lines.
+#[inline]
+fn print_section(heading: &str, lines: Vec<String>) {
+    if !lines.is_empty() {
+        println!("{heading}:");
+        for line in lines {
+            println!("    {line}");
+        }
+    }
+}
+
+/// Prints the changes in the unmatched participants of kind `K` from `diff`.
+#[inline]
+fn print_unmatched<K>(names: &Names, diff: &MatchingDiff)
+where
+    K: Kind,
+{
+    let lines = |indices: &[Index<K>]| {
+        indices
+            .iter()
+            .map(|i| names.label(*i).to_string())
+            .collect()
+    };
+    let changes = diff.unmatched::<K>();
+    print_section(
+        &format!("Newly Unmatched {}s", K::dynamic()),
+        lines(changes.newly_unmatched()),
+    );
+    print_section(
+        &format!("No Longer Unmatched {}s", K::dynamic()),
+        lines(changes.no_longer_unmatched()),
+    );
+}
+
+/// Prints the `diff` between the `old` and `new` matchings, substituting `names` for indices.
+#[inline]
+pub fn print_diff(names: &Names, old: &MatchingSet, new: &MatchingSet, diff: &MatchingDiff) {
+    if diff.is_empty() {
+        println!("The matchings are the same.");
+        return;
+    }
+    let pairs = |pairs: &[(BigIndex, LittleIndex)]| {
+        pairs
+            .iter()
+            .map(|(big, little)| format!("{}: {}", names.label(*big), names.label(*little)))
+            .collect()
+    };
+    print_section(
+        "Moved",
+        diff.moved()
+            .iter()
+            .map(|m| {
+                format!(
+                    "{}: {} -> {}",
+                    names.label(m.little),
+                    names.label(m.from),
+                    names.label(m.to)
+                )
+            })
+            .collect(),
+    );
+    print_section("Added", pairs(diff.added()));
+    print_section("Removed", pairs(diff.removed()));
+    let littles = |matching_set: &MatchingSet, big| {
+        matching_set
+            .matching(big)
+            .map(|m| {
+                m.littles
+                    .iter()
+                    .map(|l| names.label(*l).to_string())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            })
+            .unwrap_or_default()
+    };
+    print_section(
+        "Reordered",
+        diff.reordered()
+            .iter()
+            .map(|big| {
+                format!(
+                    "{}: {} -> {}",
+                    names.label(*big),
+                    littles(old, *big),
+                    littles(new, *big)
+                )
+            })
+            .collect(),
+    );
+    print_unmatched::<Big>(names, diff);
+    print_unmatched::<Little>(names, diff);
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    /// Checks that a matching with metadata and co-bigs survives a round trip through the CSV and
+    /// JSON formats.
+    #[test]
+    fn round_trip() {
+        let mut names = Names::default();
+        let alice = names.insert::<Big>("Alice".to_string()).unwrap();
+        let bob = names.insert::<Big>("Bob".to_string()).unwrap();
+        let dan = names.insert::<Little>("Dan".to_string()).unwrap();
+        let erin = names.insert::<Little>("Erin".to_string()).unwrap();
+        let mut metadata = Metadata::default();
+        metadata.insert(alice, "Email".to_string(), "alice@example.com".to_string());
+        let mut matching_set = MatchingSet::from_parts([(alice, dan)], [bob], [erin]);
+        matching_set.set_co_bigs(alice, [bob]);
+        for extension in ["csv", "json"] {
+            let path = std::env::temp_dir().join(format!("biglittle-round-trip.{extension}"));
+            write_output(path.clone(), &names, &metadata, &matching_set).unwrap();
+            let contents = fs::read_to_string(&path).unwrap();
+            assert!(contents.contains("alice@example.com"));
+            let mut read_names = Names::default();
+            let read = read_result(path.clone(), &mut read_names).unwrap();
+            fs::remove_file(path).unwrap();
+            assert_eq!(read, matching_set);
+            assert_eq!(read.co_bigs(bob).into_iter().collect::<Vec<_>>(), [alice]);
+            assert_eq!(read_names.get(bob).map(String::as_str), Some("Bob"));
+        }
+    }
+}
@@ -0,0 +1,233 @@
+//! Matching Differences
+//!
+//! Compares two [`MatchingSet`]s over the same participants, like the results of two runs with
+//! tweaked inputs, and reports which littles were added, removed, or moved between bigs, which
+//! bigs ranked their littles in a different order, and who entered or left the unmatched sets.
+
+use crate::{
+    Big, BigIndex, Index, Kind, Little, LittleIndex, MatchingSet, Select, SelectBase, SelectType,
+};
+use alloc::vec::Vec;
+use indexmap::{IndexMap, IndexSet};
+
+/// Moved Little
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct Move {
+    /// Little Index
+    pub little: LittleIndex,
+
+    /// Old Big Index
+    pub from: BigIndex,
+
+    /// New Big Index
+    pub to: BigIndex,
+}
+
+/// Unmatched Set Changes
+#[derive(derivative::Derivative)]
+#[derivative(Clone, Debug, Default(bound = ""), Eq, PartialEq)]
+pub struct UnmatchedChanges<K>
+where
+    K: Kind,
+{
+    /// Newly Unmatched Participants
+    newly_unmatched: Vec<Index<K>>,
+
+    /// No Longer Unmatched Participants
+    no_longer_unmatched: Vec<Index<K>>,
+}
+
+impl<K> UnmatchedChanges<K>
+where
+    K: Kind,
+{
+    /// Compares the `old` and `new` unmatched sets.
+    #[inline]
+    fn new(old: &IndexSet<Index<K>>, new: &IndexSet<Index<K>>) -> Self {
+        Self {
+            newly_unmatched: new.difference(old).copied().collect(),
+            no_longer_unmatched: old.difference(new).copied().collect(),
+        }
+    }
+
+    /// Returns the participants which are unmatched in the new matching but not in the old one.
+    #[inline]
+    pub fn newly_unmatched(&self) -> &[Index<K>] {
+        &self.newly_unmatched
+    }
+
+    /// Returns the participants which were unmatched in the old matching but not in the new one.
+    #[inline]
+    pub fn no_longer_unmatched(&self) -> &[Index<K>] {
+        &self.no_longer_unmatched
+    }
+
+    /// Returns `true` if the unmatched set did not change.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.newly_unmatched.is_empty() && self.no_longer_unmatched.is_empty()
+    }
+}
+
+/// Matching Difference
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct MatchingDiff {
+    /// Added Pairs
+    added: Vec<(BigIndex, LittleIndex)>,
+
+    /// Removed Pairs
+    removed: Vec<(BigIndex, LittleIndex)>,
+
+    /// Moved Littles
+    moved: Vec<Move>,
+
+    /// Reordered Bigs
+    reordered: Vec<BigIndex>,
+
+    /// Unmatched Big Changes
+    unmatched_bigs: UnmatchedChanges<Big>,
+
+    /// Unmatched Little Changes
+    unmatched_littles: UnmatchedChanges<Little>,
+}
+
+impl MatchingDiff {
+    /// Compares the `old` matching with the `new` one. If `ignore_order` is `true`, changes in
+    /// the order of the littles of a big are not reported.
+    #[inline]
+    pub fn new(old: &MatchingSet, new: &MatchingSet, ignore_order: bool) -> Self {
+        let old_bigs = old.pairs().map(|(b, l)| (l, b)).collect::<IndexMap<_, _>>();
+        let new_bigs = new.pairs().map(|(b, l)| (l, b)).collect::<IndexMap<_, _>>();
+        let mut removed = Vec::new();
+        let mut moved = Vec::new();
+        for (little, from) in &old_bigs {
+            match new_bigs.get(little) {
+                Some(to) if to != from => moved.push(Move {
+                    little: *little,
+                    from: *from,
+                    to: *to,
+                }),
+                None => removed.push((*from, *little)),
+                _ => {}
+            }
+        }
+        let added = new
+            .pairs()
+            .filter(|(_, little)| !old_bigs.contains_key(little))
+            .collect();
+        let mut reordered = Vec::new();
+        if !ignore_order {
+            for matching in old.matches() {
+                if let Some(other) = new.matching(matching.big) {
+                    let kept = matching
+                        .littles
+                        .iter()
+                        .filter(|l| other.littles.contains(*l));
+                    let ordered = other
+                        .littles
+                        .iter()
+                        .filter(|l| matching.littles.contains(*l));
+                    if !kept.eq(ordered) {
+                        reordered.push(matching.big);
+                    }
+                }
+            }
+        }
+        Self {
+            added,
+            removed,
+            moved,
+            reordered,
+            unmatched_bigs: UnmatchedChanges::new(old.unmatched_bigs(), new.unmatched_bigs()),
+            unmatched_littles: UnmatchedChanges::new(
+                old.unmatched_littles(),
+                new.unmatched_littles(),
+            ),
+        }
+    }
+
+    /// Returns the pairs of littles who were not matched in the old matching, ordered as in the
+    /// new matching.
+    #[inline]
+    pub fn added(&self) -> &[(BigIndex, LittleIndex)] {
+        &self.added
+    }
+
+    /// Returns the pairs of littles who are not matched in the new matching, ordered as in the
+    /// old matching.
+    #[inline]
+    pub fn removed(&self) -> &[(BigIndex, LittleIndex)] {
+        &self.removed
+    }
+
+    /// Returns the littles who are matched with a different big in the new matching, ordered as
+    /// in the old matching.
+    #[inline]
+    pub fn moved(&self) -> &[Move] {
+        &self.moved
+    }
+
+    /// Returns the bigs who kept some of their littles but list them in a different order in the
+    /// new matching, sorted by big index. This is always empty when the order is ignored.
+    #[inline]
+    pub fn reordered(&self) -> &[BigIndex] {
+        &self.reordered
+    }
+
+    /// Returns the changes in the unmatched participants of kind `K`.
+    #[inline]
+    pub fn unmatched<K>(&self) -> &UnmatchedChanges<K>
+    where
+        K: Kind,
+    {
+        K::select(self)
+    }
+
+    /// Returns `true` if the two matchings are the same.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty()
+            && self.removed.is_empty()
+            && self.moved.is_empty()
+            && self.reordered.is_empty()
+            && self.unmatched_bigs.is_empty()
+            && self.unmatched_littles.is_empty()
+    }
+}
+
+impl<K> SelectBase<K> for MatchingDiff
+where
+    K: Kind,
+{
+    type Type = UnmatchedChanges<K>;
+}
+
+impl<K> Select<K> for MatchingDiff
+where
+    K: Kind,
+{
+    #[inline]
+    fn big(&self) -> &SelectType<Big, Self> {
+        &self.unmatched_bigs
+    }
+
+    #[inline]
+    fn little(&self) -> &SelectType<Little, Self> {
+        &self.unmatched_littles
+    }
+}
+
+impl MatchingSet {
+    /// Compares `self` as the old matching with the `new` one. See [`MatchingDiff`] for more.
+    #[inline]
+    pub fn diff(&self, new: &MatchingSet) -> MatchingDiff {
+        MatchingDiff::new(self, new, false)
+    }
+
+    /// Compares `self` as the old matching with the `new` one, ignoring changes in the order of
+    /// the littles of each big. See [`MatchingDiff`] for more.
+    #[inline]
+    pub fn diff_ignoring_order(&self, new: &MatchingSet) -> MatchingDiff {
+        MatchingDiff::new(self, new, true)
+    }
+}
@@ -0,0 +1,236 @@
+//! Graphviz DOT Rendering
+//!
+//! Renders a [`PreferenceTable`] as an undirected bipartite graph in the Graphviz DOT language,
+//! with the bigs on one side, the littles on the other, and an edge for every pair in which at
+//! least one participant ranks the other. Edges are labeled and colored by rank, and when a
+//! [`MatchingSet`] is given, its pairs are drawn in bold and its unmatched participants are grayed
+//! out.
+
+use crate::{
+    render::{rank, Label, Rank},
+    BigIndex, DynamicKind, Index, Kind, LittleIndex, MatchingSet, Names, PreferenceTable,
+};
+use alloc::string::String;
+use core::fmt;
+
+/// Edge Colors from the Best to the Worst Rank
+const RANK_COLORS: [&str; 5] = ["#1a9850", "#91cf60", "#fee08b", "#fc8d59", "#d73027"];
+
+/// Preference Table DOT Display
+#[derive(derivative::Derivative)]
+#[derivative(Debug(bound = "T: fmt::Debug"))]
+pub struct PreferenceTableDot<'s, T = String> {
+    /// Preference Table
+    table: &'s PreferenceTable,
+
+    /// Names
+    names: &'s Names<T>,
+
+    /// Matching Set
+    matching_set: Option<&'s MatchingSet>,
+}
+
+impl<'s, T> PreferenceTableDot<'s, T> {
+    /// Highlights the pairs of `matching_set` and styles its unmatched participants differently.
+    #[inline]
+    pub fn matching(mut self, matching_set: &'s MatchingSet) -> Self {
+        self.matching_set = Some(matching_set);
+        self
+    }
+
+    /// Returns `true` if `index` is unmatched in the matching set, if there is one.
+    #[inline]
+    fn is_unmatched<K>(&self, index: Index<K>) -> bool
+    where
+        K: Kind,
+    {
+        self.matching_set
+            .map(|m| match K::dynamic() {
+                DynamicKind::Big => m.unmatched_bigs().contains(&BigIndex::new(index.index)),
+                DynamicKind::Little => m
+                    .unmatched_littles()
+                    .contains(&LittleIndex::new(index.index)),
+            })
+            .unwrap_or(false)
+    }
+
+    /// Returns `true` if `big` is matched with `little` in the matching set, if there is one.
+    #[inline]
+    fn is_matched(&self, big: BigIndex, little: LittleIndex) -> bool {
+        self.matching_set
+            .and_then(|m| m.matching(big))
+            .map(|m| m.littles.contains(&little))
+            .unwrap_or(false)
+    }
+
+    /// Writes the node of the participant at `index` with its `name`.
+    #[inline]
+    fn write_node<K>(&self, f: &mut fmt::Formatter, index: Index<K>, name: &Label<T>) -> fmt::Result
+    where
+        K: Kind,
+        T: fmt::Display,
+    {
+        write!(f, "        {} [label = {}", NodeId(index), DotId(name))?;
+        if self.is_unmatched(index) {
+            write!(f, ", style = \"dashed,filled\", fillcolor = lightgray")?;
+        }
+        writeln!(f, "];")
+    }
+
+    /// Writes the edge between `big` and `little`, where `big_rank` is the rank `big` gives to
+    /// `little` and `little_rank` the rank `little` gives to `big`, if any.
+    #[inline]
+    fn write_edge(
+        &self,
+        f: &mut fmt::Formatter,
+        big: BigIndex,
+        little: LittleIndex,
+        big_rank: Option<usize>,
+        little_rank: Option<usize>,
+    ) -> fmt::Result {
+        let worst = big_rank.max(little_rank).unwrap_or(1);
+        write!(
+            f,
+            "    {} -- {} [label = \"{}/{}\", color = \"{}\"",
+            NodeId(big),
+            NodeId(little),
+            Rank(big_rank),
+            Rank(little_rank),
+            RANK_COLORS[(worst - 1).min(RANK_COLORS.len() - 1)],
+        )?;
+        if big_rank.is_none() || little_rank.is_none() {
+            write!(f, ", style = dashed")?;
+        }
+        if self.is_matched(big, little) {
+            write!(f, ", penwidth = 3")?;
+        }
+        writeln!(f, "];")
+    }
+}
+
+impl<'s, T> Clone for PreferenceTableDot<'s, T> {
+    #[inline]
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<'s, T> Copy for PreferenceTableDot<'s, T> {}
+
+impl<'s, T> fmt::Display for PreferenceTableDot<'s, T>
+where
+    T: fmt::Display,
+{
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let table = self.table;
+        writeln!(f, "graph preferences {{\n    rankdir = LR;")?;
+        writeln!(
+            f,
+            "    subgraph bigs {{\n        rank = same;\n        node [shape = box];"
+        )?;
+        for big in 0..table.big_preferences.len() {
+            let big = BigIndex::from(big);
+            self.write_node(f, big, &self.names.label(big))?;
+        }
+        writeln!(
+            f,
+            "    }}\n    subgraph littles {{\n        rank = same;\n        node [shape = ellipse];"
+        )?;
+        for little in 0..table.little_preferences.len() {
+            let little = LittleIndex::from(little);
+            self.write_node(f, little, &self.names.label(little))?;
+        }
+        writeln!(f, "    }}")?;
+        for (big, littles) in table.big_preferences.iter().enumerate() {
+            let big = BigIndex::from(big);
+            for (position, little) in littles.iter().enumerate() {
+                let little_rank = rank(table, *little, big);
+                self.write_edge(f, big, *little, Some(position + 1), little_rank)?;
+            }
+        }
+        for (little, bigs) in table.little_preferences.iter().enumerate() {
+            let little = LittleIndex::from(little);
+            for (position, big) in bigs.iter().enumerate() {
+                let ranked = table
+                    .big_preferences
+                    .get(big.index as usize)
+                    .is_some_and(|row| row.contains(&little));
+                if !ranked {
+                    self.write_edge(f, *big, little, None, Some(position + 1))?;
+                }
+            }
+        }
+        write!(f, "}}")
+    }
+}
+
+impl PreferenceTable {
+    /// Returns a [`Display`](fmt::Display) implementation for `self` which writes the preference
+    /// table as a Graphviz DOT graph, substituting `names` for indices. See [`PreferenceTableDot`]
+    /// to highlight a matching.
+    #[inline]
+    pub fn dot<'s, T>(&'s self, names: &'s Names<T>) -> PreferenceTableDot<'s, T>
+    where
+        T: fmt::Display,
+    {
+        PreferenceTableDot {
+            table: self,
+            names,
+            matching_set: None,
+        }
+    }
+}
+
+/// DOT Node Identifier
+///
+/// Writes the index of a participant as a DOT identifier which is unique across both kinds.
+struct NodeId<K>(Index<K>)
+where
+    K: Kind;
+
+impl<K> fmt::Display for NodeId<K>
+where
+    K: Kind,
+{
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match K::dynamic() {
+            DynamicKind::Big => write!(f, "big{}", self.0.index),
+            DynamicKind::Little => write!(f, "little{}", self.0.index),
+        }
+    }
+}
+
+/// Graphviz DOT Identifier
+///
+/// Writes a value as a quoted DOT identifier, escaping quotes and backslashes.
+pub(crate) struct DotId<'t, T>(pub &'t T);
+
+impl<'t, T> fmt::Display for DotId<'t, T>
+where
+    T: fmt::Display,
+{
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        /// Escaping Writer
+        struct Escape<'f, 'g>(&'f mut fmt::Formatter<'g>);
+
+        impl fmt::Write for Escape<'_, '_> {
+            #[inline]
+            fn write_str(&mut self, s: &str) -> fmt::Result {
+                for c in s.chars() {
+                    if matches!(c, '"' | '\\') {
+                        self.0.write_str("\\")?;
+                    }
+                    fmt::Write::write_char(self.0, c)?;
+                }
+                Ok(())
+            }
+        }
+
+        f.write_str("\"")?;
+        fmt::write(&mut Escape(f), format_args!("{}", self.0))?;
+        f.write_str("\"")
+    }
+}
@@ -0,0 +1,240 @@
+//! Dynamic Kind API
+//!
+//! The rest of the library is generic over the [`Kind`] of each participant. This module mirrors
+//! the parts of it that are useful from data-driven or foreign code, using [`DynIndex`] to carry
+//! the kind of a participant at runtime. Every conversion back to a typed [`Index`] checks the
+//! kind and fails with a [`KindMismatch`] if it is wrong.
+
+use crate::{
+    trace::{Event, Trace},
+    Big, DynamicKind, Index, Kind, Little, MatchingSet, Names, PreferenceTable,
+};
+use alloc::vec::Vec;
+use core::{fmt, hash::Hash};
+use indexmap::Equivalent;
+
+/// Kind Mismatch Error
+///
+/// Returned when a [`DynIndex`] has a different kind than the one required.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct KindMismatch {
+    /// Expected Kind
+    pub expected: DynamicKind,
+
+    /// Found Kind
+    pub found: DynamicKind,
+}
+
+impl KindMismatch {
+    /// Checks that `found` is the same as `expected`.
+    #[inline]
+    pub fn check(expected: DynamicKind, found: DynamicKind) -> Result<(), Self> {
+        if expected == found {
+            Ok(())
+        } else {
+            Err(Self { expected, found })
+        }
+    }
+}
+
+impl fmt::Display for KindMismatch {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "Expected a {} index but found a {} index.",
+            self.expected, self.found
+        )
+    }
+}
+
+#[cfg(feature = "std")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "std")))]
+impl std::error::Error for KindMismatch {}
+
+/// Dynamic Matching Index
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct DynIndex {
+    /// Kind
+    pub kind: DynamicKind,
+
+    /// Index
+    pub index: u32,
+}
+
+impl DynIndex {
+    /// Builds a new [`DynIndex`] from `kind` and `index`.
+    #[inline]
+    pub fn new(kind: DynamicKind, index: u32) -> Self {
+        Self { kind, index }
+    }
+
+    /// Converts `self` into a typed [`Index`] if it has the kind `K`.
+    #[inline]
+    pub fn typed<K>(self) -> Result<Index<K>, KindMismatch>
+    where
+        K: Kind,
+    {
+        KindMismatch::check(K::dynamic(), self.kind)?;
+        Ok(Index::new(self.index))
+    }
+}
+
+impl<K> From<Index<K>> for DynIndex
+where
+    K: Kind,
+{
+    #[inline]
+    fn from(index: Index<K>) -> Self {
+        Self::new(K::dynamic(), index.index)
+    }
+}
+
+impl<K> TryFrom<DynIndex> for Index<K>
+where
+    K: Kind,
+{
+    type Error = KindMismatch;
+
+    #[inline]
+    fn try_from(index: DynIndex) -> Result<Self, Self::Error> {
+        index.typed()
+    }
+}
+
+impl<T> Names<T> {
+    /// Returns the name associated to `index` if it is contained in the set.
+    #[inline]
+    pub fn get_dyn(&self, index: DynIndex) -> Option<&T> {
+        match index.kind {
+            DynamicKind::Big => self.get(Index::<Big>::new(index.index)),
+            DynamicKind::Little => self.get(Index::<Little>::new(index.index)),
+        }
+    }
+}
+
+impl<T> Names<T>
+where
+    T: Eq + Hash,
+{
+    /// Inserts a new `name` with the given `kind`. This method returns `None` if `name` is
+    /// contained in the opposite variant. See [`insert`](Self::insert) for more.
+    #[inline]
+    pub fn insert_dyn(&mut self, kind: DynamicKind, name: T) -> Option<DynIndex> {
+        match kind {
+            DynamicKind::Big => self.insert::<Big>(name).map(Into::into),
+            DynamicKind::Little => self.insert::<Little>(name).map(Into::into),
+        }
+    }
+
+    /// Returns the index of the `name` in `self` if it has an entry of the given `kind`.
+    #[inline]
+    pub fn index_dyn<Q>(&self, kind: DynamicKind, name: &Q) -> Option<DynIndex>
+    where
+        Q: Equivalent<T> + Hash + ?Sized,
+    {
+        match kind {
+            DynamicKind::Big => self.index::<Big>(name).map(Into::into),
+            DynamicKind::Little => self.index::<Little>(name).map(Into::into),
+        }
+    }
+
+    /// Returns the index of the `name` in `self` whichever kind it was inserted with.
+    #[inline]
+    pub fn find<Q>(&self, name: &Q) -> Option<DynIndex>
+    where
+        Q: Equivalent<T> + Hash + ?Sized,
+    {
+        self.index_dyn(DynamicKind::Big, name)
+            .or_else(|| self.index_dyn(DynamicKind::Little, name))
+    }
+}
+
+impl PreferenceTable {
+    /// Inserts the `preferences` as the next row of the given `kind` in the preference table,
+    /// checking that every preference has the opposite kind. Nothing is inserted if any of them
+    /// do not. See [`insert`](Self::insert) for more.
+    #[inline]
+    pub fn insert_dyn<I>(&mut self, kind: DynamicKind, preferences: I) -> Result<(), KindMismatch>
+    where
+        I: IntoIterator<Item = DynIndex>,
+    {
+        match kind {
+            DynamicKind::Big => self.insert::<Big, _>(typed_all::<Little, _>(preferences)?),
+            DynamicKind::Little => self.insert::<Little, _>(typed_all::<Big, _>(preferences)?),
+        }
+        Ok(())
+    }
+
+    /// Returns the preferences of the participant at `index` if it has a row in the table.
+    #[inline]
+    pub fn get_dyn(&self, index: DynIndex) -> Option<Vec<DynIndex>> {
+        match index.kind {
+            DynamicKind::Big => row::<Big>(self, index.index),
+            DynamicKind::Little => row::<Little>(self, index.index),
+        }
+    }
+}
+
+impl MatchingSet {
+    /// Returns the participants matched with the participant at `index`. Bigs can have many
+    /// partners while littles have at most one.
+    #[inline]
+    pub fn partners_dyn(&self, index: DynIndex) -> Vec<DynIndex> {
+        match index.kind {
+            DynamicKind::Big => self
+                .matching(Index::new(index.index))
+                .into_iter()
+                .flat_map(|m| m.littles.iter().copied().map(Into::into))
+                .collect(),
+            DynamicKind::Little => {
+                let little = Index::<Little>::new(index.index);
+                self.matches
+                    .iter()
+                    .filter(|m| m.littles.contains(&little))
+                    .map(|m| m.big.into())
+                    .collect()
+            }
+        }
+    }
+}
+
+impl Event {
+    /// Returns `true` if the participant at `index` is involved in `self`.
+    #[inline]
+    pub fn contains_dyn(&self, index: DynIndex) -> bool {
+        match index.kind {
+            DynamicKind::Big => self.contains(Index::<Big>::new(index.index)),
+            DynamicKind::Little => self.contains(Index::<Little>::new(index.index)),
+        }
+    }
+}
+
+impl Trace {
+    /// Returns an iterator over the events in `self` which involve the participant at `index`.
+    #[inline]
+    pub fn events_for_dyn(&self, index: DynIndex) -> impl Iterator<Item = &Event> {
+        self.events().iter().filter(move |e| e.contains_dyn(index))
+    }
+}
+
+/// Converts every index in `indices` into a typed [`Index`] of kind `K`.
+#[inline]
+fn typed_all<K, I>(indices: I) -> Result<Vec<Index<K>>, KindMismatch>
+where
+    K: Kind,
+    I: IntoIterator<Item = DynIndex>,
+{
+    indices.into_iter().map(DynIndex::typed).collect()
+}
+
+/// Returns the row of kind `K` at `index` from `table` as dynamic indices.
+#[inline]
+fn row<K>(table: &PreferenceTable, index: u32) -> Option<Vec<DynIndex>>
+where
+    K: Kind,
+{
+    K::select(table)
+        .get(index as usize)
+        .map(|row| row.iter().copied().map(Into::into).collect())
+}
@@ -0,0 +1,241 @@
+//! Feasibility Analysis
+//!
+//! Checks before matching whether every little can be matched, given the capacities of the bigs
+//! and any forbidden or forced pairs already applied to the [`PreferenceTable`]. When some littles
+//! must be left out, the analysis explains why: either a little has no mutually-acceptable big
+//! with any room at all, or a group of littles only lists bigs who together do not have room for
+//! all of them, a violation of Hall's condition.
+
+use crate::{BigIndex, LittleIndex, MatchingSet, PreferenceTable};
+use alloc::vec::Vec;
+use indexmap::IndexSet;
+
+/// Hall's Condition Violation
+///
+/// A group of littles whose mutually-acceptable bigs together have room for fewer littles than
+/// there are in the group.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct HallViolation {
+    /// Littles
+    littles: IndexSet<LittleIndex>,
+
+    /// Mutually-Acceptable Bigs
+    bigs: IndexSet<BigIndex>,
+
+    /// Total Capacity of the Bigs
+    capacity: usize,
+}
+
+impl HallViolation {
+    /// Returns the littles in the group, sorted by index.
+    #[inline]
+    pub fn littles(&self) -> &IndexSet<LittleIndex> {
+        &self.littles
+    }
+
+    /// Returns every big that any little in the group who is not [locked](PreferenceTable::lock)
+    /// is mutually acceptable with, sorted by index.
+    #[inline]
+    pub fn bigs(&self) -> &IndexSet<BigIndex> {
+        &self.bigs
+    }
+
+    /// Returns the total number of littles that the bigs have room for.
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Returns the number of littles in the group which must be left unmatched.
+    #[inline]
+    pub fn deficit(&self) -> usize {
+        self.littles.len().saturating_sub(self.capacity)
+    }
+
+    /// Merges `other` into `self` if they share any big, returning `other` back if they do not.
+    #[inline]
+    fn merge(&mut self, other: Self) -> Option<Self> {
+        if self.bigs.is_disjoint(&other.bigs) {
+            return Some(other);
+        }
+        self.littles.extend(other.littles);
+        self.bigs.extend(other.bigs);
+        None
+    }
+}
+
+/// Feasibility Report
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Feasibility {
+    /// Number of Littles
+    little_count: usize,
+
+    /// Maximum Matching Size
+    maximum_matched: usize,
+
+    /// Unmatchable Littles
+    unmatchable: Vec<LittleIndex>,
+
+    /// Hall's Condition Violations
+    violations: Vec<HallViolation>,
+
+    /// Groups of Littles Locked to Different Co-Big Units
+    lock_conflicts: Vec<Vec<LittleIndex>>,
+}
+
+impl Feasibility {
+    /// Analyzes the feasibility of matching every little in `table`.
+    ///
+    /// # Algorithm
+    ///
+    /// First a matching with the largest possible number of matched littles is found, keeping
+    /// every [locked](PreferenceTable::lock) pair. If some little `l` is still unmatched, the
+    /// littles and bigs reachable from `l` by alternating between a mutually-acceptable big and
+    /// the littles matched with it, without moving any locked little, form a Hall's condition
+    /// violation: every one of those bigs is full, since otherwise `l` could be matched along an
+    /// augmenting path, and together they are matched with every little in the group except the
+    /// unmatched ones. Violations which share a big are merged into one.
+    ///
+    /// With co-big units and little groups, the analysis runs on whole units and groups as
+    /// described in the [`group`](crate::group) module, and every group is listed with all of its
+    /// littles. Since groups of different sizes may not fit into the room that is left, a
+    /// violation can then have enough capacity in total and still leave littles unmatched. Groups
+    /// whose littles are locked to different units are only listed as
+    /// [lock conflicts](Self::lock_conflicts).
+    #[inline]
+    pub fn new(table: &PreferenceTable) -> Self {
+        let original = table;
+        let lock_conflicts = table.lock_conflicts();
+        let reduced = table.reduced();
+        let table = reduced.as_ref().unwrap_or(table);
+        let matching_set = table.maximum_matching();
+        let mut unmatchable = Vec::new();
+        let mut violations = Vec::<HallViolation>::new();
+        for little in &matching_set.unmatched_littles {
+            if lock_conflicts.iter().any(|group| group.contains(little)) {
+                continue;
+            }
+            let weight = table.weight(*little);
+            if !mutual_bigs(table, *little).any(|big| table.has_room(big, 0, weight)) {
+                unmatchable.extend(original.group(*little));
+                continue;
+            }
+            if violations.iter().any(|v| v.littles.contains(little)) {
+                continue;
+            }
+            let mut violation = alternating_group(table, &matching_set, *little);
+            violations = violations
+                .into_iter()
+                .filter_map(|other| violation.merge(other))
+                .collect();
+            violations.push(violation);
+        }
+        for violation in &mut violations {
+            violation.littles = violation
+                .littles
+                .iter()
+                .flat_map(|l| original.group(*l))
+                .collect();
+            violation.littles.sort();
+            violation.bigs.sort();
+            violation.capacity = violation
+                .bigs
+                .iter()
+                .map(|big| table.capacity(*big).unwrap_or_default())
+                .sum();
+        }
+        Self {
+            little_count: table.little_preferences.len(),
+            maximum_matched: original.expand(matching_set).pairs().count(),
+            unmatchable,
+            violations,
+            lock_conflicts,
+        }
+    }
+
+    /// Returns the number of littles in the table.
+    #[inline]
+    pub fn little_count(&self) -> usize {
+        self.little_count
+    }
+
+    /// Returns the largest number of littles that can be matched at the same time.
+    #[inline]
+    pub fn maximum_matched(&self) -> usize {
+        self.maximum_matched
+    }
+
+    /// Returns `true` if every little can be matched at the same time.
+    #[inline]
+    pub fn is_feasible(&self) -> bool {
+        self.maximum_matched == self.little_count
+    }
+
+    /// Returns the littles who cannot be matched under any assignment because none of their
+    /// mutually-acceptable bigs have any room.
+    #[inline]
+    pub fn unmatchable(&self) -> &[LittleIndex] {
+        &self.unmatchable
+    }
+
+    /// Returns the groups of littles who only list bigs without enough room for all of them.
+    #[inline]
+    pub fn violations(&self) -> &[HallViolation] {
+        &self.violations
+    }
+
+    /// Returns the groups of littles who are [locked](PreferenceTable::lock) to bigs in different
+    /// co-big units and can never be matched together. See
+    /// [`lock_conflicts`](PreferenceTable::lock_conflicts) for more.
+    #[inline]
+    pub fn lock_conflicts(&self) -> &[Vec<LittleIndex>] {
+        &self.lock_conflicts
+    }
+}
+
+/// Returns an iterator over the bigs that `little` is mutually acceptable with in `table`.
+#[inline]
+fn mutual_bigs(
+    table: &PreferenceTable,
+    little: LittleIndex,
+) -> impl Iterator<Item = BigIndex> + '_ {
+    table.little_preferences[little.index as usize]
+        .iter()
+        .copied()
+        .filter(move |big| big.preference(little, table).is_some())
+}
+
+/// Returns the littles and bigs reachable from the unmatched `little` by alternating between
+/// mutually-acceptable bigs and the littles matched with them in `matching_set`.
+#[inline]
+fn alternating_group(
+    table: &PreferenceTable,
+    matching_set: &MatchingSet,
+    little: LittleIndex,
+) -> HallViolation {
+    let mut group = HallViolation::default();
+    group.littles.insert(little);
+    let mut cursor = 0;
+    while let Some(little) = group.littles.get_index(cursor).copied() {
+        cursor += 1;
+        if table.is_locked(little) {
+            continue;
+        }
+        for big in mutual_bigs(table, little) {
+            if group.bigs.insert(big) {
+                if let Some(matching) = matching_set.matching(big) {
+                    group.littles.extend(matching.littles.iter().copied());
+                }
+            }
+        }
+    }
+    group
+}
+
+impl PreferenceTable {
+    /// Analyzes the [`Feasibility`] of matching every little in `self`.
+    #[inline]
+    pub fn feasibility(&self) -> Feasibility {
+        Feasibility::new(self)
+    }
+}
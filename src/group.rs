@@ -0,0 +1,482 @@
+//! Co-Big Units and Little Groups
+//!
+//! Some pairings come in twins: two bigs who share their littles, or a little who wants to be
+//! matched together with a specific co-little. Bigs [paired](PreferenceTable::pair_co_bigs) into a
+//! co-big unit act as one matching slot whose [capacity](PreferenceTable::set_capacity) is the sum
+//! of their capacities, and littles [grouped](PreferenceTable::group_littles) together are always
+//! matched with the same unit, or left unmatched together.
+//!
+//! The solvers handle both by reducing the table, without changing any index. The big with the
+//! lowest index in each unit stands for the whole unit: its preferences interleave the preferences
+//! of every member of the unit, best first, and the littles rank it wherever they rank their
+//! favorite member. A unit and a little only rank each other if the little ranks and is ranked by
+//! at least one member of the unit. The little with the lowest index in each group stands for the
+//! whole group: it only keeps the units which every member of the group ranks in this way, in its
+//! own order, and it counts as the size of the group towards the capacity of its unit.
+//! [Locks](PreferenceTable::lock) are moved to these representatives, and a group whose littles
+//! are locked to different units is left unmatched and reported as a
+//! [lock conflict](PreferenceTable::lock_conflicts).
+//!
+//! After solving, each little matched with a unit is attributed to one member of the unit who
+//! ranks them back, preferring the member they are locked to and then the member with the most
+//! room left, so that every reported pair is mutually acceptable. Every member with attributed
+//! littles gets their own [`Matching`], which lists the rest of the unit as its
+//! [co-bigs](Matching::co_bigs), and the other members are listed with the unmatched bigs.
+//!
+//! The [events](crate::trace::Event) of the reduced table are reported for every member of the
+//! group of their little, and for the members of the unit of their big who are locked to or rank
+//! that little, so that the outcome of any member can be explained.
+
+use crate::{
+    trace::{Event, Observer},
+    BigIndex, Index, Kind, LittleIndex, Matching, MatchingSet, PreferenceTable,
+};
+use alloc::{vec, vec::Vec};
+use indexmap::{IndexMap, IndexSet};
+
+impl PreferenceTable {
+    /// Pairs `big` with `co_big` into a co-big unit which is matched as one big, merging their
+    /// units if either of them is already paired with other bigs.
+    #[inline]
+    pub fn pair_co_bigs(&mut self, big: BigIndex, co_big: BigIndex) {
+        let mut members = self.unit(big);
+        members.extend(self.unit(co_big));
+        join(&mut self.units, members);
+    }
+
+    /// Returns every big in the co-big unit of `big`, sorted by index, which is only `big` if they
+    /// are not paired with anyone.
+    #[inline]
+    pub fn unit(&self, big: BigIndex) -> Vec<BigIndex> {
+        members(&self.units, big)
+    }
+
+    /// Groups the `littles` so that they are always matched with the same big or co-big unit,
+    /// merging the groups of any of them who are already grouped with other littles.
+    #[inline]
+    pub fn group_littles<I>(&mut self, littles: I)
+    where
+        I: IntoIterator<Item = LittleIndex>,
+    {
+        let mut members = Vec::new();
+        for little in littles {
+            members.extend(self.group(little));
+        }
+        join(&mut self.groups, members);
+    }
+
+    /// Returns every little in the group of `little`, sorted by index, which is only `little` if
+    /// they are not grouped with anyone.
+    #[inline]
+    pub fn group(&self, little: LittleIndex) -> Vec<LittleIndex> {
+        members(&self.groups, little)
+    }
+
+    /// Returns every group of littles whose members are [locked](Self::lock) to bigs in different
+    /// co-big units, sorted by their first little. Such a group can never be matched together, so
+    /// the solvers leave it unmatched.
+    #[inline]
+    pub fn lock_conflicts(&self) -> Vec<Vec<LittleIndex>> {
+        let mut leads = self.groups.values().copied().collect::<IndexSet<_>>();
+        leads.sort();
+        leads
+            .into_iter()
+            .map(|lead| self.group(lead))
+            .filter(|group| {
+                group
+                    .iter()
+                    .filter_map(|l| self.locked(*l))
+                    .map(|b| self.unit_lead(b))
+                    .collect::<IndexSet<_>>()
+                    .len()
+                    > 1
+            })
+            .collect()
+    }
+
+    /// Returns the big which stands for the co-big unit of `big`.
+    #[inline]
+    fn unit_lead(&self, big: BigIndex) -> BigIndex {
+        self.units.get(&big).copied().unwrap_or(big)
+    }
+
+    /// Returns the little which stands for the group of `little`.
+    #[inline]
+    fn group_lead(&self, little: LittleIndex) -> LittleIndex {
+        self.groups.get(&little).copied().unwrap_or(little)
+    }
+
+    /// Returns `true` if `big` and `little` rank each other.
+    #[inline]
+    fn is_mutual(&self, big: BigIndex, little: LittleIndex) -> bool {
+        big.preference(little, self).is_some() && little.preference(big, self).is_some()
+    }
+
+    /// Returns `true` if every little in the group of `little` ranks and is ranked by at least one
+    /// member of the co-big unit of `big`.
+    #[inline]
+    fn accepts(&self, big: BigIndex, little: LittleIndex) -> bool {
+        let unit = self.unit(big);
+        self.group(little)
+            .into_iter()
+            .all(|l| unit.iter().any(|b| self.is_mutual(*b, l)))
+    }
+
+    /// Returns the members of the co-big unit of `big` who an event between the unit and `little`
+    /// is reported for: the member `little` is locked to, or else the members who rank each other
+    /// with `little`, the members `little` ranks, or the whole unit, whichever is found first.
+    #[inline]
+    fn event_bigs(&self, big: BigIndex, little: LittleIndex) -> Vec<BigIndex> {
+        let unit = self.unit(big);
+        if let Some(big) = self.locked(little).filter(|b| unit.contains(b)) {
+            return vec![big];
+        }
+        let mutual = unit
+            .iter()
+            .copied()
+            .filter(|b| self.is_mutual(*b, little))
+            .collect::<Vec<_>>();
+        if !mutual.is_empty() {
+            return mutual;
+        }
+        let ranked = unit
+            .iter()
+            .copied()
+            .filter(|b| little.preference(*b, self).is_some())
+            .collect::<Vec<_>>();
+        if ranked.is_empty() {
+            unit
+        } else {
+            ranked
+        }
+    }
+
+    /// Returns the member of a co-big unit who `little` is attributed to, given the `littles`
+    /// already attributed to each member, or `None` if the unit has no members. This is the big
+    /// `little` is locked to if it is in the unit, or else the member who ranks `little` back with
+    /// the most room left, falling back to the big that another little in their group is locked
+    /// to, or the lead of the unit.
+    #[inline]
+    fn attribute(
+        &self,
+        littles: &IndexMap<BigIndex, Vec<LittleIndex>>,
+        little: LittleIndex,
+    ) -> Option<BigIndex> {
+        let in_unit = |big: &BigIndex| littles.contains_key(big);
+        if let Some(big) = self.locked(little).filter(in_unit) {
+            return Some(big);
+        }
+        littles
+            .iter()
+            .filter(|(big, _)| self.is_mutual(**big, little))
+            .min_by_key(|(big, attributed)| {
+                (!self.has_room(**big, attributed.len(), 1), attributed.len())
+            })
+            .map(|(big, _)| *big)
+            .or_else(|| {
+                self.group(little)
+                    .into_iter()
+                    .find_map(|l| self.locked(l).filter(in_unit))
+            })
+            .or_else(|| littles.keys().next().copied())
+    }
+
+    /// Returns the table reduced to whole co-big units and little groups, or `None` if `self` has
+    /// neither. See the [`group`](crate::group) module for more.
+    #[inline]
+    pub(crate) fn reduced(&self) -> Option<Self> {
+        if self.units.is_empty() && self.groups.is_empty() {
+            return None;
+        }
+        let conflicts = self
+            .lock_conflicts()
+            .into_iter()
+            .map(|group| group[0])
+            .collect::<IndexSet<_>>();
+        let mut table = self.clone();
+        for (big, row) in table.big_preferences.iter_mut().enumerate() {
+            let big = BigIndex::from(big);
+            row.clear();
+            if self.unit_lead(big) != big {
+                continue;
+            }
+            let rows = self
+                .unit(big)
+                .into_iter()
+                .filter_map(|b| self.big_preferences.get(b.index as usize))
+                .collect::<Vec<_>>();
+            let mut merged = IndexSet::new();
+            for position in 0..rows.iter().map(|r| r.len()).max().unwrap_or_default() {
+                for little in rows.iter().filter_map(|r| r.get(position)) {
+                    let lead = self.group_lead(*little);
+                    if !conflicts.contains(&lead) && self.accepts(big, lead) {
+                        merged.insert(lead);
+                    }
+                }
+            }
+            row.extend(merged);
+        }
+        for (little, row) in table.little_preferences.iter_mut().enumerate() {
+            let little = LittleIndex::from(little);
+            row.clear();
+            if self.group_lead(little) != little || conflicts.contains(&little) {
+                continue;
+            }
+            let units = self
+                .little_preferences
+                .get(little.index as usize)
+                .into_iter()
+                .flatten()
+                .map(|b| self.unit_lead(*b))
+                .filter(|b| self.accepts(*b, little))
+                .collect::<IndexSet<_>>();
+            row.extend(units);
+        }
+        table.capacities.clear();
+        for big in 0..self.big_preferences.len() {
+            let big = BigIndex::from(big);
+            if self.unit_lead(big) == big {
+                let capacity = self.unit(big).into_iter().map(|b| self.capacity(b)).sum();
+                if let Some(capacity) = capacity {
+                    table.capacities.insert(big, capacity);
+                }
+            }
+        }
+        table.locks.clear();
+        for (little, big) in &self.locks {
+            let lead = self.group_lead(*little);
+            if !conflicts.contains(&lead) {
+                table.locks.insert(lead, self.unit_lead(*big));
+            }
+        }
+        for little in self.groups.keys() {
+            table.weights.insert(*little, 0);
+        }
+        for lead in self.groups.values() {
+            *table.weights.entry(*lead).or_default() += 1;
+        }
+        table.units.clear();
+        table.groups.clear();
+        Some(table)
+    }
+
+    /// Expands a `matching_set` of the [reduced](Self::reduced) table back to every member of the
+    /// co-big units and little groups of `self`.
+    #[inline]
+    pub(crate) fn expand(&self, matching_set: MatchingSet) -> MatchingSet {
+        if self.units.is_empty() && self.groups.is_empty() {
+            return matching_set;
+        }
+        let mut matches = Vec::new();
+        let mut unmatched_bigs = matching_set.unmatched_bigs.clone();
+        for matching in &matching_set.matches {
+            let unit = self.unit(matching.big);
+            let mut littles = unit
+                .iter()
+                .map(|b| (*b, Vec::new()))
+                .collect::<IndexMap<_, _>>();
+            for little in matching.littles.iter().flat_map(|l| self.group(*l)) {
+                let big = self.attribute(&littles, little);
+                if let Some(attributed) = big.and_then(|b| littles.get_mut(&b)) {
+                    attributed.push(little);
+                }
+            }
+            for (big, mut attributed) in littles {
+                if attributed.is_empty() {
+                    unmatched_bigs.insert(big);
+                    continue;
+                }
+                unmatched_bigs.shift_remove(&big);
+                attributed.sort_by_key(|l| {
+                    let preference = big.preference(*l, self);
+                    (preference.is_none(), preference)
+                });
+                matches.push(Matching {
+                    big,
+                    littles: attributed.into_iter().collect(),
+                    co_bigs: unit.iter().copied().filter(|b| *b != big).collect(),
+                });
+            }
+        }
+        matches.sort_by_key(|m| m.big);
+        unmatched_bigs.sort();
+        let unmatched_littles = matching_set
+            .unmatched_littles
+            .iter()
+            .flat_map(|l| self.group(*l))
+            .collect();
+        MatchingSet {
+            matches,
+            unmatched_bigs,
+            unmatched_littles,
+        }
+    }
+}
+
+impl MatchingSet {
+    /// Returns the other bigs in the [co-big unit](PreferenceTable::pair_co_bigs) of `big`, sorted
+    /// by index. These are the [co-bigs](Matching::co_bigs) of the matching of `big`, or of the
+    /// matching of another member of the unit if no little was attributed to `big`.
+    #[inline]
+    pub fn co_bigs(&self, big: BigIndex) -> IndexSet<BigIndex> {
+        if let Some(matching) = self.matching(big) {
+            return matching.co_bigs.clone();
+        }
+        let mut co_bigs = self
+            .matches
+            .iter()
+            .find(|m| m.co_bigs.contains(&big))
+            .into_iter()
+            .flat_map(|m| m.co_bigs.iter().copied().chain([m.big]))
+            .filter(|b| *b != big)
+            .collect::<IndexSet<_>>();
+        co_bigs.sort();
+        co_bigs
+    }
+
+    /// Records the `co_bigs` in the matching of `big`, like the co-bigs of a matching read back
+    /// from a file. Does nothing if `big` is not matched with any little.
+    #[inline]
+    pub fn set_co_bigs<I>(&mut self, big: BigIndex, co_bigs: I)
+    where
+        I: IntoIterator<Item = BigIndex>,
+    {
+        if let Ok(index) = self.matches.binary_search_by_key(&big, |m| m.big) {
+            self.matches[index].co_bigs = co_bigs.into_iter().filter(|b| *b != big).collect();
+        }
+    }
+
+    /// Maps `self` onto the [reduced](PreferenceTable::reduced) version of `table`, moving every
+    /// pair to the big which stands for its unit and dropping the littles who do not stand for
+    /// their group.
+    #[inline]
+    pub(crate) fn reduced(&self, table: &PreferenceTable) -> Self {
+        let is_lead = |little: &LittleIndex| table.group_lead(*little) == *little;
+        Self::from_parts(
+            self.pairs()
+                .filter(|(_, little)| is_lead(little))
+                .map(|(big, little)| (table.unit_lead(big), little)),
+            self.unmatched_bigs
+                .iter()
+                .copied()
+                .filter(|big| table.unit_lead(*big) == *big),
+            self.unmatched_littles.iter().copied().filter(is_lead),
+        )
+    }
+}
+
+/// Expanding Observer
+///
+/// Passes the events of a solver on to `observer`, mapping them back to the members of the co-big
+/// units and little groups of `table` if the solver runs on its [reduced](PreferenceTable::reduced)
+/// version. See the [`group`](crate::group) module for more.
+pub(crate) struct Expanding<'t, 'o, O> {
+    /// Preference Table, if the Solver runs on its Reduced Version
+    pub(crate) table: Option<&'t PreferenceTable>,
+
+    /// Observer
+    pub(crate) observer: &'o mut O,
+}
+
+impl<'t, 'o, O> Observer for Expanding<'t, 'o, O>
+where
+    O: Observer,
+{
+    #[inline]
+    fn observe(&mut self, event: Event) {
+        let table = match self.table {
+            Some(table) => table,
+            _ => return self.observer.observe(event),
+        };
+        if let Event::UnmatchedBig { big } = event {
+            for big in table.unit(big) {
+                self.observer.observe(Event::UnmatchedBig { big });
+            }
+            return;
+        }
+        for little in event.little().map(|l| table.group(l)).unwrap_or_default() {
+            let bigs = |big| table.event_bigs(big, little);
+            match event {
+                Event::Locked { big, .. } => {
+                    for big in bigs(big) {
+                        self.observer.observe(Event::Locked { little, big });
+                    }
+                }
+                Event::Proposal { big, .. } => {
+                    for big in bigs(big) {
+                        self.observer.observe(Event::Proposal { little, big });
+                    }
+                }
+                Event::Acceptance { big, .. } => {
+                    for big in bigs(big) {
+                        self.observer.observe(Event::Acceptance { little, big });
+                    }
+                }
+                Event::Rejection { big, .. } => {
+                    for big in bigs(big) {
+                        self.observer.observe(Event::Rejection { little, big });
+                    }
+                }
+                Event::Full { big, .. } => {
+                    for big in bigs(big) {
+                        self.observer.observe(Event::Full { little, big });
+                    }
+                }
+                Event::Exhaustion { .. } => self.observer.observe(Event::Exhaustion { little }),
+                Event::Shift { from, to, .. } => {
+                    for from in bigs(from) {
+                        for to in bigs(to) {
+                            self.observer.observe(Event::Shift { little, from, to });
+                        }
+                    }
+                }
+                Event::Matched { big, .. } => {
+                    for big in bigs(big) {
+                        self.observer.observe(Event::Matched { little, big });
+                    }
+                }
+                Event::UnmatchedLittle { .. } => {
+                    self.observer.observe(Event::UnmatchedLittle { little })
+                }
+                Event::UnmatchedBig { .. } => {}
+            }
+        }
+    }
+}
+
+/// Joins the `members` into one unit of `leads`, led by the member with the lowest index.
+#[inline]
+fn join<K>(leads: &mut IndexMap<Index<K>, Index<K>>, mut members: Vec<Index<K>>)
+where
+    K: Kind,
+{
+    members.sort();
+    members.dedup();
+    if members.len() < 2 {
+        return;
+    }
+    let lead = members[0];
+    for member in members {
+        leads.insert(member, lead);
+    }
+}
+
+/// Returns every member of the unit of `index` in `leads`, sorted by index.
+#[inline]
+fn members<K>(leads: &IndexMap<Index<K>, Index<K>>, index: Index<K>) -> Vec<Index<K>>
+where
+    K: Kind,
+{
+    match leads.get(&index) {
+        Some(lead) => {
+            let mut members = leads
+                .iter()
+                .filter(|(_, l)| *l == lead)
+                .map(|(m, _)| *m)
+                .collect::<Vec<_>>();
+            members.sort();
+            members
+        }
+        _ => vec![index],
+    }
+}
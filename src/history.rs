@@ -0,0 +1,218 @@
+//! Matching History
+//!
+//! Records the pairs of past seasons by participant name, since the indices of the same person
+//! change from one season to the next, so that a new season can forbid or penalize repeating a
+//! pairing that already happened, or matching a little with the same family again. The family of
+//! a big for a little is everyone linked to the big by a chain of past pairings which does not go
+//! through the little, so a little who had Alice is matched into the family of Alice again by
+//! getting Alice's big or one of Alice's littles.
+
+use crate::{BigIndex, LittleIndex, MatchingSet, Names, PreferenceTable};
+use alloc::{string::String, vec::Vec};
+use core::{hash::Hash, iter};
+use indexmap::{IndexMap, IndexSet};
+
+/// Repeat Pairing Policy
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Deserialize, serde::Serialize),
+    serde(rename_all = "kebab-case")
+)]
+pub enum RepeatPolicy {
+    /// Forbids every pairing which already happened in a past season, and penalizes matching a
+    /// little with a family they already had like [`Penalize`](Self::Penalize).
+    Forbid,
+
+    /// Moves every pairing which already happened to the end of the preferences of both
+    /// participants, the more often it happened the further down, so that it is only chosen when
+    /// there is no other way to match them. Among the rest, the bigs from a family which a little
+    /// already had, and the littles who already had the family of a big, move down the same way.
+    #[default]
+    Penalize,
+}
+
+/// Matching Season
+#[derive(derivative::Derivative)]
+#[derivative(
+    Clone(bound = "T: Clone"),
+    Debug(bound = "T: core::fmt::Debug"),
+    Default(bound = ""),
+    Eq(bound = "T: Eq"),
+    PartialEq(bound = "T: PartialEq")
+)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct Season<T = String> {
+    /// Season Label
+    pub label: String,
+
+    /// Matched Pairs of Big and Little Names
+    pub pairs: Vec<(T, T)>,
+}
+
+impl<T> Season<T> {
+    /// Builds a new [`Season`] called `label` from the pairs of `matching_set`, substituting
+    /// `names` for indices. Every little of a big with [co-bigs](crate::Matching::co_bigs) is also
+    /// paired with each of their co-bigs, since the whole co-big unit shares its littles.
+    #[inline]
+    pub fn new(label: String, matching_set: &MatchingSet, names: &Names<T>) -> Self
+    where
+        T: Clone,
+    {
+        Self {
+            label,
+            pairs: matching_set
+                .matches()
+                .iter()
+                .flat_map(|m| {
+                    let bigs = iter::once(m.big).chain(m.co_bigs.iter().copied());
+                    bigs.flat_map(move |big| m.littles.iter().map(move |little| (big, *little)))
+                })
+                .filter_map(|(big, little)| {
+                    Some((names.get(big)?.clone(), names.get(little)?.clone()))
+                })
+                .collect(),
+        }
+    }
+}
+
+/// Matching History
+#[derive(derivative::Derivative)]
+#[derivative(
+    Clone(bound = "T: Clone"),
+    Debug(bound = "T: core::fmt::Debug"),
+    Default(bound = ""),
+    Eq(bound = "T: Eq"),
+    PartialEq(bound = "T: PartialEq")
+)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct History<T = String> {
+    /// Seasons
+    seasons: Vec<Season<T>>,
+}
+
+impl<T> History<T> {
+    /// Returns the seasons in `self`, from oldest to newest.
+    #[inline]
+    pub fn seasons(&self) -> &[Season<T>] {
+        &self.seasons
+    }
+
+    /// Appends `season` as the newest season of `self`.
+    #[inline]
+    pub fn push(&mut self, season: Season<T>) {
+        self.seasons.push(season);
+    }
+
+    /// Returns the number of times `big` was matched with `little`, counting every pair listed in
+    /// every past season.
+    #[inline]
+    pub fn count(&self, big: &T, little: &T) -> usize
+    where
+        T: PartialEq,
+    {
+        self.seasons
+            .iter()
+            .flat_map(|s| &s.pairs)
+            .filter(|(b, l)| b == big && l == little)
+            .count()
+    }
+
+    /// Returns the number of times each little of `table` was matched with each big of `table`,
+    /// followed by the number of times they were matched with someone from the family of the big,
+    /// counting every pair listed in every past season and looking up the participants by their
+    /// `names`. Pairings which never happened in either way are left out.
+    #[inline]
+    fn repeats(
+        &self,
+        table: &PreferenceTable,
+        names: &Names<T>,
+    ) -> IndexMap<(BigIndex, LittleIndex), (usize, usize)>
+    where
+        T: Eq + Hash,
+    {
+        let pairs = self.seasons.iter().flat_map(|s| &s.pairs);
+        let mut members = IndexSet::new();
+        let edges = pairs
+            .map(|(big, little)| (members.insert_full(big).0, members.insert_full(little).0))
+            .collect::<Vec<_>>();
+        let mut repeats = IndexMap::<_, (usize, usize)>::new();
+        for little in (0..table.little_preferences.len()).map(LittleIndex::from) {
+            let position = match names.get(little).and_then(|l| members.get_index_of(l)) {
+                Some(position) => position,
+                _ => continue,
+            };
+            let mut families = (0..members.len()).collect::<Vec<_>>();
+            for (big, other) in &edges {
+                if *big != position && *other != position {
+                    let (big, other) = (root(&mut families, *big), root(&mut families, *other));
+                    families[big] = other;
+                }
+            }
+            let past_bigs = edges
+                .iter()
+                .filter(|(_, l)| *l == position)
+                .map(|(b, _)| *b)
+                .collect::<Vec<_>>();
+            for big in (0..table.big_preferences.len()).map(BigIndex::from) {
+                let member = match names.get(big).and_then(|b| members.get_index_of(b)) {
+                    Some(member) => member,
+                    _ => continue,
+                };
+                let family = root(&mut families, member);
+                let (mut count, mut family_count) = (0, 0);
+                for past_big in &past_bigs {
+                    count += usize::from(*past_big == member);
+                    family_count += usize::from(root(&mut families, *past_big) == family);
+                }
+                if family_count > 0 {
+                    repeats.insert((big, little), (count, family_count));
+                }
+            }
+        }
+        repeats
+    }
+
+    /// Applies the `policy` to every pairing in `table` which already happened in `self`, looking
+    /// up the participants of `table` by their `names`.
+    #[inline]
+    pub fn apply(&self, table: &mut PreferenceTable, names: &Names<T>, policy: RepeatPolicy)
+    where
+        T: Eq + Hash,
+    {
+        let repeats = self.repeats(table, names);
+        let penalty = |big, little| repeats.get(&(big, little)).copied().unwrap_or_default();
+        for (big, row) in table.big_preferences.iter_mut().enumerate() {
+            let big = BigIndex::from(big);
+            row.sort_by_key(|little| penalty(big, *little));
+        }
+        for (little, row) in table.little_preferences.iter_mut().enumerate() {
+            let little = LittleIndex::from(little);
+            row.sort_by_key(|big| penalty(*big, little));
+        }
+        if policy == RepeatPolicy::Forbid {
+            for ((big, little), (count, _)) in repeats {
+                if count > 0 {
+                    table.forbid(big, little);
+                }
+            }
+        }
+    }
+}
+
+/// Returns the root of the family of `member` in the union-find forest of `families`, pointing
+/// every member on the way directly at the root.
+#[inline]
+fn root(families: &mut [usize], member: usize) -> usize {
+    let mut root = member;
+    while families[root] != root {
+        root = families[root];
+    }
+    let mut member = member;
+    while families[member] != root {
+        let next = families[member];
+        families[member] = root;
+        member = next;
+    }
+    root
+}
@@ -8,8 +8,32 @@
 extern crate alloc;
 
 use alloc::{string::String, vec::Vec};
-use core::{cmp::Ordering, fmt, marker::PhantomData, num::NonZeroU32};
-use indexmap::{map::Entry, IndexMap, IndexSet};
+use core::{
+    cmp::{Ordering, Reverse},
+    fmt,
+    hash::Hash,
+    marker::PhantomData,
+    num::NonZeroU32,
+};
+use group::Expanding;
+use indexmap::{Equivalent, IndexMap, IndexSet};
+use render::{width, write_names, write_padded, Alignment, Label, Truncate, UnitLabel};
+use trace::{Event, Observer};
+
+pub mod diff;
+pub mod dot;
+pub mod dynamic;
+pub mod feasibility;
+pub mod group;
+pub mod history;
+pub mod lineage;
+pub mod metadata;
+pub mod rematch;
+pub mod render;
+pub mod report;
+pub mod stability;
+pub mod stats;
+pub mod trace;
 
 /// Sealed Module
 mod sealed {
@@ -18,7 +42,7 @@ mod sealed {
 }
 
 /// Dynamic Kind
-#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
 pub enum DynamicKind {
     /// Big Kind
     Big,
@@ -27,6 +51,24 @@ pub enum DynamicKind {
     Little,
 }
 
+impl DynamicKind {
+    /// Returns the opposite kind of `self`.
+    #[inline]
+    pub fn opposite(self) -> Self {
+        match self {
+            Self::Big => Self::Little,
+            Self::Little => Self::Big,
+        }
+    }
+}
+
+impl fmt::Display for DynamicKind {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Debug::fmt(self, f)
+    }
+}
+
 /// Select Base
 pub trait SelectBase<K>
 where
@@ -64,7 +106,7 @@ where
 }
 
 /// Selection Kind
-pub trait Kind: sealed::Sealed + Sized {
+pub trait Kind: 'static + sealed::Sealed + Sized {
     /// Opposite Kind
     ///
     /// For bigs the opposite kind are littles, and for littles the opposite kind are bigs.
@@ -159,14 +201,16 @@ where
         }
     }
 
-    /// Returns the preference rank of `other` for `self` using `table`.
+    /// Returns the preference rank of `other` for `self` using `table`, or `None` if `self` does
+    /// not rank `other` or is not in `table`.
     #[inline]
     pub fn preference(
         self,
         other: Index<K::Opposite>,
         table: &PreferenceTable,
     ) -> Option<Preference<K>> {
-        K::select(table)[self.index as usize]
+        K::select(table)
+            .get(self.index as usize)?
             .iter()
             .position(|i| *i == other)
             .and_then(|i| NonZeroU32::new((i + 1) as u32).map(Preference::new))
@@ -242,7 +286,15 @@ where
 }
 
 /// Names
-#[derive(Debug, Default)]
+///
+/// Assigns an [`Index`] to every participant identifier of type `T`, which is a [`String`] name by
+/// default but can be any hashable type like a student ID or a record with a name and an email.
+#[derive(derivative::Derivative)]
+#[derivative(
+    Clone(bound = "T: Clone"),
+    Debug(bound = "T: fmt::Debug"),
+    Default(bound = "")
+)]
 pub struct Names<T = String> {
     /// Big Names
     bigs: IndexSet<T>,
@@ -251,60 +303,111 @@ pub struct Names<T = String> {
     littles: IndexSet<T>,
 }
 
-impl Names {
-    /// Insert a new `name` with the given kind `K`. This method returns `None` if `name` is
-    /// contained in the opposite variant.
+impl<T> Names<T> {
+    /// Returns the name associated to `index` if it is contained in the set.
     #[inline]
-    pub fn insert<K>(&mut self, name: String) -> Option<Index<K>>
+    pub fn get<K>(&self, index: Index<K>) -> Option<&T>
     where
         K: Kind,
     {
-        if K::Opposite::select(self).contains(&name) {
-            return None;
+        K::select(self).get_index(index.index as usize)
+    }
+
+    /// Returns a [`Label`] for `index` which displays its name, or `#<index>` if there is no name
+    /// for `index`.
+    #[inline]
+    pub fn label<K>(&self, index: Index<K>) -> Label<'_, T>
+    where
+        K: Kind,
+    {
+        match self.get(index) {
+            Some(name) => Label::Name(name),
+            _ => Label::Unknown(index.index),
         }
-        Some(K::select_mut(self).insert_full(name).0.into())
     }
 
-    /// Returns the name associated to `index` if it is contained in the set.
+    /// Returns the number of names of kind `K`.
     #[inline]
-    pub fn get<K>(&self, index: Index<K>) -> Option<&String>
+    pub fn len<K>(&self) -> usize
     where
         K: Kind,
     {
-        K::select(self).get_index(index.index as usize)
+        K::select(self).len()
+    }
+
+    /// Returns `true` if there are no names of either kind.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.bigs.is_empty() && self.littles.is_empty()
+    }
+
+    /// Returns an iterator over the indices and names of kind `K` in the order they were inserted.
+    #[inline]
+    pub fn iter<K>(&self) -> impl Iterator<Item = (Index<K>, &T)>
+    where
+        K: Kind,
+    {
+        K::select(self)
+            .iter()
+            .enumerate()
+            .map(|(i, name)| (i.into(), name))
+    }
+}
+
+impl<T> Names<T>
+where
+    T: Eq + Hash,
+{
+    /// Insert a new `name` with the given kind `K`. This method returns `None` if `name` is
+    /// contained in the opposite variant.
+    #[inline]
+    pub fn insert<K>(&mut self, name: T) -> Option<Index<K>>
+    where
+        K: Kind,
+    {
+        if K::Opposite::select(self).contains(&name) {
+            return None;
+        }
+        Some(K::select_mut(self).insert_full(name).0.into())
     }
 
-    /// Returns the index of the `name` in `self` if it has an entry of the kind `K`.
+    /// Returns the index of the `name` in `self` if it has an entry of the kind `K`. The `name`
+    /// can be given as any borrowed form of `T`, like a `&str` for `String` names.
     #[inline]
-    pub fn index<K>(&self, name: &str) -> Option<Index<K>>
+    pub fn index<K>(&self, name: &(impl Equivalent<T> + Hash + ?Sized)) -> Option<Index<K>>
     where
         K: Kind,
     {
         K::select(self).get_index_of(name).map(Into::into)
     }
+}
 
-    /// Finds the length of the longest name of kind `K`.
+impl<T> Names<T>
+where
+    T: fmt::Display,
+{
+    /// Finds the display width of the longest name of kind `K`, up to `max_width` if there is one.
     #[inline]
-    fn longest_name_length<K>(&self) -> usize
+    fn longest_name_length<K>(&self, max_width: Option<usize>) -> usize
     where
         K: Kind,
     {
         K::select(self)
             .iter()
-            .map(String::len)
+            .map(|name| width(&Truncate(name, max_width)))
             .max()
             .unwrap_or_default()
     }
 }
 
-impl<K> SelectBase<K> for Names
+impl<K, T> SelectBase<K> for Names<T>
 where
     K: Kind,
 {
-    type Type = IndexSet<String>;
+    type Type = IndexSet<T>;
 }
 
-impl<K> Select<K> for Names
+impl<K, T> Select<K> for Names<T>
 where
     K: Kind,
 {
@@ -319,7 +422,7 @@ where
     }
 }
 
-impl<K> SelectMut<K> for Names
+impl<K, T> SelectMut<K> for Names<T>
 where
     K: Kind,
 {
@@ -342,6 +445,27 @@ pub struct PreferenceTable {
 
     /// Little Preferences
     little_preferences: SelectType<Little, Self>,
+
+    /// Big Capacities
+    capacities: IndexMap<BigIndex, usize>,
+
+    /// Locked Pairs
+    locks: IndexMap<LittleIndex, BigIndex>,
+
+    /// Big Families
+    families: IndexMap<BigIndex, usize>,
+
+    /// Family Sizes
+    family_sizes: IndexMap<usize, usize>,
+
+    /// Co-Big Units
+    units: IndexMap<BigIndex, BigIndex>,
+
+    /// Little Groups
+    groups: IndexMap<LittleIndex, LittleIndex>,
+
+    /// Little Weights
+    weights: IndexMap<LittleIndex, usize>,
 }
 
 impl PreferenceTable {
@@ -352,33 +476,258 @@ impl PreferenceTable {
         K: Kind,
         I: IntoIterator<Item = Index<K::Opposite>>,
     {
-        K::select_mut(self).insert(Vec::from_iter(preferences));
+        K::select_mut(self).push(Vec::from_iter(preferences));
+    }
+
+    /// Sets the largest number of littles that `big` can be matched with. Bigs without a capacity
+    /// can be matched with every little they rank.
+    #[inline]
+    pub fn set_capacity(&mut self, big: BigIndex, capacity: usize) {
+        self.capacities.insert(big, capacity);
+    }
+
+    /// Returns the largest number of littles that `big` can be matched with if it is limited.
+    #[inline]
+    pub fn capacity(&self, big: BigIndex) -> Option<usize> {
+        self.capacities.get(&big).copied()
+    }
+
+    /// Returns `true` if `big` has room for `weight` more littles when it is already matched with
+    /// `load` littles.
+    #[inline]
+    fn has_room(&self, big: BigIndex, load: usize, weight: usize) -> bool {
+        self.capacity(big)
+            .is_none_or(|capacity| load + weight <= capacity)
+    }
+
+    /// Returns the number of littles that `little` stands for, which is the size of their
+    /// [group](Self::group_littles) in a reduced table and one otherwise.
+    #[inline]
+    fn weight(&self, little: LittleIndex) -> usize {
+        self.weights.get(&little).copied().unwrap_or(1)
+    }
+
+    /// Returns `true` if `little` is part of a group in a reduced table but does not stand for it.
+    #[inline]
+    fn is_follower(&self, little: LittleIndex) -> bool {
+        self.weight(little) == 0
+    }
+
+    /// Returns the number of littles matched with `big` in `matching_set`, counting each of them
+    /// by their weight.
+    #[inline]
+    fn load(&self, matching_set: &MatchingSet, big: BigIndex) -> usize {
+        matching_set
+            .matching(big)
+            .map_or(0, |m| m.littles.iter().map(|l| self.weight(*l)).sum())
+    }
+
+    /// Replaces the preferences of the participant at `index` with `preferences`.
+    #[inline]
+    pub fn set_preferences<K, I>(&mut self, index: Index<K>, preferences: I)
+    where
+        K: Kind,
+        I: IntoIterator<Item = Index<K::Opposite>>,
+    {
+        if let Some(row) = K::select_mut(self).get_mut(index.index as usize) {
+            *row = Vec::from_iter(preferences);
+        }
+    }
+
+    /// Withdraws the participant at `index` by clearing their preferences and removing them from
+    /// the preferences of every participant of the opposite kind. The indices of the other
+    /// participants do not change, and the withdrawn participant is left unmatched by every
+    /// solver.
+    #[inline]
+    pub fn withdraw<K>(&mut self, index: Index<K>)
+    where
+        K: Kind,
+    {
+        if let Some(row) = K::select_mut(self).get_mut(index.index as usize) {
+            row.clear();
+        }
+        for row in K::Opposite::select_mut(self) {
+            row.retain(|i| i.index != index.index);
+        }
+    }
+
+    /// Forbids matching `big` with `little` by removing each of them from the preferences of the
+    /// other.
+    #[inline]
+    pub fn forbid(&mut self, big: BigIndex, little: LittleIndex) {
+        if let Some(row) = self.big_preferences.get_mut(big.index as usize) {
+            row.retain(|l| *l != little);
+        }
+        if let Some(row) = self.little_preferences.get_mut(little.index as usize) {
+            row.retain(|b| *b != big);
+        }
+    }
+
+    /// Forces `little` to be matched with `big` by making `big` the only preference of `little`,
+    /// adding `little` to the end of the preferences of `big` if it is missing, and removing
+    /// `little` from the preferences of every other big. The solvers still respect the capacity
+    /// of `big`, so `little` can end up unmatched if `big` is already full with other forced
+    /// littles.
+    #[inline]
+    pub fn force(&mut self, big: BigIndex, little: LittleIndex) {
+        if let Some(row) = self.little_preferences.get_mut(little.index as usize) {
+            row.clear();
+            row.push(big);
+        }
+        for (i, row) in self.big_preferences.iter_mut().enumerate() {
+            if i == big.index as usize {
+                if !row.contains(&little) {
+                    row.push(little);
+                }
+            } else {
+                row.retain(|l| *l != little);
+            }
+        }
+    }
+
+    /// Puts `big` into the `family`, so that the [even matching](Self::find_even_matching) evens
+    /// out the sizes of whole families instead of the number of littles of each big. Bigs without
+    /// a family are a family of their own.
+    #[inline]
+    pub fn set_family(&mut self, big: BigIndex, family: usize) {
+        self.families.insert(big, family);
+    }
+
+    /// Returns the family of `big` if it has one.
+    #[inline]
+    pub fn family(&self, big: BigIndex) -> Option<usize> {
+        self.families.get(&big).copied()
+    }
+
+    /// Sets the number of members that the `family` already has before any little is matched
+    /// with one of its bigs. Families start out empty by default.
+    #[inline]
+    pub fn set_family_size(&mut self, family: usize, size: usize) {
+        self.family_sizes.insert(family, size);
+    }
+
+    /// Returns `true` if `lhs` and `rhs` are the same big or in the same family.
+    #[inline]
+    fn same_family(&self, lhs: BigIndex, rhs: BigIndex) -> bool {
+        lhs == rhs
+            || self
+                .family(lhs)
+                .is_some_and(|family| self.family(rhs) == Some(family))
+    }
+
+    /// Returns the size of the family of `big` in `matching_set`, which is the number of littles of
+    /// `big` if it has no family.
+    #[inline]
+    fn family_load(&self, matching_set: &MatchingSet, big: BigIndex) -> usize {
+        match self.family(big) {
+            Some(family) => {
+                self.family_sizes.get(&family).copied().unwrap_or_default()
+                    + matching_set
+                        .matches
+                        .iter()
+                        .filter(|m| self.family(m.big) == Some(family))
+                        .map(|m| self.load(matching_set, m.big))
+                        .sum::<usize>()
+            }
+            _ => self.load(matching_set, big),
+        }
+    }
+
+    /// Locks `little` to `big`, so that every solver keeps them matched and never moves `little`,
+    /// even if they do not rank each other. Locked littles count against the
+    /// [capacity](Self::set_capacity) of their big, and the rest of the littles are matched
+    /// around them. Locking a little which is already locked replaces their big.
+    #[inline]
+    pub fn lock(&mut self, big: BigIndex, little: LittleIndex) {
+        self.locks.insert(little, big);
+    }
+
+    /// Locks every one of the `pairs`, like the confirmed matches of a previous
+    /// [`MatchingSet`]. See [`lock`](Self::lock) for more.
+    #[inline]
+    pub fn lock_pairs<I>(&mut self, pairs: I)
+    where
+        I: IntoIterator<Item = (BigIndex, LittleIndex)>,
+    {
+        for (big, little) in pairs {
+            self.lock(big, little);
+        }
+    }
+
+    /// Unlocks `little`, returning the big they were locked to if there was one.
+    #[inline]
+    pub fn unlock(&mut self, little: LittleIndex) -> Option<BigIndex> {
+        self.locks.shift_remove(&little)
+    }
+
+    /// Returns the big that `little` is locked to if there is one.
+    #[inline]
+    pub fn locked(&self, little: LittleIndex) -> Option<BigIndex> {
+        self.locks.get(&little).copied()
+    }
+
+    /// Returns an iterator over every locked pair, in the order they were locked.
+    #[inline]
+    pub fn locks(&self) -> impl Iterator<Item = (BigIndex, LittleIndex)> + '_ {
+        self.locks.iter().map(|(little, big)| (*big, *little))
+    }
+
+    /// Returns `true` if `little` is locked to a big in `self`.
+    #[inline]
+    fn is_locked(&self, little: LittleIndex) -> bool {
+        self.locked(little)
+            .is_some_and(|big| (big.index as usize) < self.big_preferences.len())
+    }
+
+    /// Inserts every locked pair whose big and little are both in `self` into the `matching_set`,
+    /// reporting each of them to the `observer`.
+    #[inline]
+    fn insert_locks<O>(&self, matching_set: &mut MatchingSet, observer: &mut O)
+    where
+        O: Observer,
+    {
+        for (big, little) in self.locks() {
+            if (little.index as usize) < self.little_preferences.len() && self.is_locked(little) {
+                observer.observe(Event::Locked { little, big });
+                matching_set.insert_match(self, big, little);
+            }
+        }
     }
 
     /// Updates the `matching_set` by choosing from the preferences of `little` and seeing if any of
     /// the bigs in that ordering also prefer `little`. If not, the `little` is unmatched.
     #[inline]
-    fn update_matching<'i, I>(&self, matching_set: &mut MatchingSet, little: LittleIndex, bigs: I)
-    where
+    fn update_matching<'i, I, O>(
+        &self,
+        matching_set: &mut MatchingSet,
+        little: LittleIndex,
+        bigs: I,
+        observer: &mut O,
+    ) where
         I: IntoIterator<Item = &'i BigIndex>,
+        O: Observer,
     {
-        let mut bigs = bigs.into_iter();
-        loop {
-            if let Some(big) = bigs.next() {
-                if big.preference(little, self).is_some() {
-                    matching_set.insert_match(self, *big, little);
-                    break;
+        for big in bigs {
+            observer.observe(Event::Proposal { little, big: *big });
+            if big.preference(little, self).is_some() {
+                if !self.has_room(*big, self.load(matching_set, *big), self.weight(little)) {
+                    observer.observe(Event::Full { little, big: *big });
+                    continue;
                 }
-            } else {
-                matching_set.unmatched_littles.insert(little);
-                break;
+                observer.observe(Event::Acceptance { little, big: *big });
+                matching_set.insert_match(self, *big, little);
+                return;
             }
+            observer.observe(Event::Rejection { little, big: *big });
         }
+        observer.observe(Event::Exhaustion { little });
+        matching_set.unmatched_littles.insert(little);
     }
 
     /// Collects all the unmatched bigs relative to `matching_set` and declares them as unmatched.
     #[inline]
     fn collect_unmatched_bigs(&self, matching_set: &mut MatchingSet) {
+        matching_set.unmatched_bigs.clear();
         for big in 0..self.big_preferences.len() {
             let big = Index::from(big);
             if !matching_set.matches.iter().any(|m| m.big == big) {
@@ -387,75 +736,338 @@ impl PreferenceTable {
         }
     }
 
+    /// Reports the final state of every participant in `matching_set` to the `observer`.
+    #[inline]
+    fn observe_final<O>(matching_set: &MatchingSet, observer: &mut O)
+    where
+        O: Observer,
+    {
+        for matching in &matching_set.matches {
+            for little in &matching.littles {
+                observer.observe(Event::Matched {
+                    little: *little,
+                    big: matching.big,
+                });
+            }
+        }
+        for little in &matching_set.unmatched_littles {
+            observer.observe(Event::UnmatchedLittle { little: *little });
+        }
+        for big in &matching_set.unmatched_bigs {
+            observer.observe(Event::UnmatchedBig { big: *big });
+        }
+    }
+
     /// Finds the maximal matching. See [`find_maximal_matching`](Self::find_maximal_matching)
     /// for more.
     #[inline]
-    fn maximal_matching(&self) -> MatchingSet {
+    fn maximal_matching<O>(&self, observer: &mut O) -> MatchingSet
+    where
+        O: Observer,
+    {
         let mut matching_set = MatchingSet::default();
+        self.insert_locks(&mut matching_set, observer);
         for (i, bigs) in self.little_preferences.iter().enumerate() {
-            self.update_matching(&mut matching_set, Index::from(i), bigs.iter());
+            let little = Index::from(i);
+            if !self.is_locked(little) && !self.is_follower(little) {
+                self.update_matching(&mut matching_set, little, bigs.iter(), observer);
+            }
         }
         matching_set
     }
 
-    /// Finds the maximal matching where littles select according to their preferences and all bigs
-    /// are assumed to have as much capacity as needed to accomodate all the littles they rank in
-    /// their preferences.
+    /// Finds the maximal matching where littles select according to their preferences and each big
+    /// accepts every little they rank until they reach their [capacity](Self::set_capacity).
+    /// Bigs without a capacity accomodate all the littles they rank in their preferences.
+    /// [Locked](Self::lock) littles are matched with their big before anyone else. Co-big units
+    /// and little groups are matched as one participant, see the [`group`] module for more.
     #[inline]
     pub fn find_maximal_matching(&self) -> MatchingSet {
-        let mut matching_set = self.maximal_matching();
-        self.collect_unmatched_bigs(&mut matching_set);
+        self.find_maximal_matching_with_observer(&mut ())
+    }
+
+    /// Finds the maximal matching, reporting every step of the algorithm to `observer`. See
+    /// [`find_maximal_matching`](Self::find_maximal_matching) for more.
+    #[inline]
+    pub fn find_maximal_matching_with_observer<O>(&self, observer: &mut O) -> MatchingSet
+    where
+        O: Observer,
+    {
+        let reduced = self.reduced();
+        let table = reduced.as_ref().unwrap_or(self);
+        let mut matching_set = table.maximal_matching(&mut Expanding {
+            table: reduced.as_ref().map(|_| self),
+            observer,
+        });
+        table.collect_unmatched_bigs(&mut matching_set);
+        let matching_set = self.expand(matching_set);
+        Self::observe_final(&matching_set, observer);
         matching_set
     }
 
+    /// Searches for a path of shifts in `matching_set` which starts at one of the bigs in `parents`
+    /// and ends at the first big found for which `is_end` holds, returning the shifts along the
+    /// path from its first big to its last. Only littles of the given `weight` are shifted, so
+    /// that the bigs in between keep their load.
+    #[inline]
+    fn shift_path<F>(
+        &self,
+        matching_set: &MatchingSet,
+        mut parents: IndexMap<BigIndex, Option<Shift>>,
+        weight: usize,
+        is_end: F,
+    ) -> Option<Vec<Shift>>
+    where
+        F: Fn(BigIndex) -> bool,
+    {
+        let mut cursor = 0;
+        while let Some((big, _)) = parents.get_index(cursor) {
+            let big = *big;
+            cursor += 1;
+            let littles = matching_set.matching(big).into_iter();
+            for little in littles.flat_map(|m| m.littles.iter().rev()) {
+                if self.is_locked(*little) || self.weight(*little) != weight {
+                    continue;
+                }
+                for next in &self.little_preferences[little.index as usize] {
+                    if parents.contains_key(next) || next.preference(*little, self).is_none() {
+                        continue;
+                    }
+                    parents.insert(
+                        *next,
+                        Some(Shift {
+                            little: *little,
+                            from: big,
+                            to: *next,
+                        }),
+                    );
+                    if is_end(*next) {
+                        let mut path = Vec::new();
+                        let mut current = *next;
+                        while let Some(Some(shift)) = parents.get(&current) {
+                            path.push(*shift);
+                            current = shift.from;
+                        }
+                        path.reverse();
+                        return Some(path);
+                    }
+                }
+            }
+        }
+        None
+    }
+
+    /// Finds an augmenting path for one of the unmatched littles in `matching_set`, returning the
+    /// little, the big it can be matched with, and the shifts which make room for it at that big.
+    /// See [`find_even_matching`](Self::find_even_matching) for more.
+    #[inline]
+    fn augmenting_path(
+        &self,
+        matching_set: &MatchingSet,
+    ) -> Option<(LittleIndex, BigIndex, Vec<Shift>)> {
+        for little in &matching_set.unmatched_littles {
+            let weight = self.weight(*little);
+            let has_room = |big| self.has_room(big, self.load(matching_set, big), weight);
+            let mut parents = IndexMap::new();
+            for big in &self.little_preferences[little.index as usize] {
+                if big.preference(*little, self).is_some() {
+                    if has_room(*big) {
+                        return Some((*little, *big, Vec::new()));
+                    }
+                    parents.insert(*big, None);
+                }
+            }
+            if let Some(path) = self.shift_path(matching_set, parents, weight, has_room) {
+                return Some((*little, path[0].from, path));
+            }
+        }
+        None
+    }
+
+    /// Finds a cost-reducing path in `matching_set`, returning the shifts along the path from its
+    /// first big to its last. See [`find_even_matching`](Self::find_even_matching) for more.
+    #[inline]
+    fn cost_reducing_path(&self, matching_set: &MatchingSet) -> Option<Vec<Shift>> {
+        let mut starts = matching_set
+            .matches
+            .iter()
+            .map(|m| (m.big, self.family_load(matching_set, m.big)))
+            .collect::<Vec<_>>();
+        starts.sort_by_key(|(_, load)| Reverse(*load));
+        for (start, load) in starts {
+            if load < 2 {
+                break;
+            }
+            let mut weights = matching_set
+                .matching(start)
+                .into_iter()
+                .flat_map(|m| m.littles.iter().map(|l| self.weight(*l)))
+                .collect::<Vec<_>>();
+            weights.sort_unstable();
+            weights.dedup();
+            for weight in weights {
+                let mut parents = IndexMap::new();
+                parents.insert(start, None);
+                let path = self.shift_path(matching_set, parents, weight, |big| {
+                    !self.same_family(start, big)
+                        && self.family_load(matching_set, big) + weight < load
+                        && self.has_room(big, self.load(matching_set, big), weight)
+                });
+                if path.is_some() {
+                    return path;
+                }
+            }
+        }
+        None
+    }
+
     /// Finds the evenly-distributed matching.
     ///
     /// # Algorithm
     ///
-    /// First a maximal matching is computed which fills all bigs to the capacity equal to the
-    /// number of preferences they allocated. Then, if there are any bigs without matches, the
-    /// fullest big sends their least prefered little to look for another match, and the little
-    /// proceeds down their ranking list to find the next big to match with. This continues until
-    /// all bigs have at least one match or all the matches have an equal number of littles,
-    /// whichever comes first.
+    /// First the [maximal matching](Self::find_maximal_matching) is computed, which assigns every
+    /// little to the first big on their list which also ranks them and still has room for them. If
+    /// some bigs have a [capacity](Self::set_capacity), a little can be left unmatched because
+    /// every big they could be matched with is full. Such littles are matched along _augmenting
+    /// paths_: chains of bigs starting at a big which is mutually acceptable with the little and
+    /// ending at a big with room, where some little matched with each big in the chain is mutually
+    /// acceptable with the next one. Shifting one little along each link of the chain makes room
+    /// for the unmatched little at the first big. Then the matching is rebalanced along
+    /// _cost-reducing paths_: chains of bigs `b_0, b_1, ..., b_k` where some little matched with
+    /// `b_i` is mutually acceptable with `b_{i + 1}`, and where `b_k` has at least two fewer
+    /// littles than `b_0` and is not at capacity. Shifting one little along each link of the chain
+    /// moves one little's worth of load from `b_0` to `b_k` and leaves the bigs in between
+    /// unchanged. [Locked](Self::lock) littles are never shifted along either kind of path. Paths
+    /// are searched from the fullest big first, preferring to shift the littles the big ranks
+    /// lowest towards the bigs those littles rank highest. The algorithm stops when there are no
+    /// cost-reducing paths left.
+    ///
+    /// # Termination
+    ///
+    /// Every augmenting path matches one more little, so there are at most `n` of them for `n`
+    /// littles, and they are all shifted before any cost-reducing path. Shifting a little from a
+    /// big with `a` littles to a big with `b <= a - 2` littles changes the sum of the squares of
+    /// the number of littles of each big by `2(b - a) + 2 <= -2`. This sum is at most `n^2` and
+    /// can never be negative, so at most `n^2 / 2` cost-reducing paths are shifted.
+    ///
+    /// # Optimality
+    ///
+    /// Shifting never unmatches a little, and once no augmenting path is left, the number of
+    /// matched littles is the largest possible within the capacities of the bigs (Berge, 1957).
+    /// Without capacities, these are exactly the littles matched by the maximal matching, i.e.
+    /// every little with at least one mutually-acceptable big. Once no cost-reducing path is left,
+    /// the assignment of the matched littles is an optimal semi-matching (Harvey, Ladner, Lovász
+    /// and Tamir, 2003): over all assignments of these littles to mutually-acceptable bigs within
+    /// their capacities, it minimizes the sum of the squares of the number of littles of each big,
+    /// and in particular, the largest number of littles assigned to a single big. With
+    /// [locked](Self::lock) pairs, both hold over the assignments which keep every locked pair.
+    ///
+    /// # Families
+    ///
+    /// If some bigs are in a [family](Self::set_family), the cost-reducing paths compare the sizes
+    /// of whole families instead of the number of littles of each big: a path must end at a big
+    /// in another family whose size is at least two smaller, counting the members the family
+    /// already had. Each such path lowers the sum of the squares of the family sizes, so the
+    /// algorithm still terminates, and it stops once no single path can even out the families
+    /// any further.
+    ///
+    /// # Co-Big Units and Little Groups
+    ///
+    /// If some bigs are [paired](Self::pair_co_bigs) or some littles are
+    /// [grouped](Self::group_littles), the algorithm runs on the table reduced to whole units and
+    /// groups described in the [`group`] module, where a group of `w` littles counts as `w` towards
+    /// the load and capacity of its unit. Paths only shift littles of the same weight, and a
+    /// cost-reducing path moving `w` littles must end at a big whose size is more than `w` smaller,
+    /// which still lowers the sum of the squares. Fitting groups of different sizes into the
+    /// capacities is a packing problem, so in this case the result is no longer guaranteed to
+    /// match the largest possible number of littles or to be optimally even.
     #[inline]
     pub fn find_even_matching(&self) -> MatchingSet {
-        let mut matching_set = self.maximal_matching();
-        let mut preference_index = IndexMap::<LittleIndex, usize>::new();
-        while let Some(matching) = matching_set.next_largest_match(self.big_preferences.len()) {
-            if let Some(little) = matching.littles.pop() {
-                let preference_index = match preference_index.entry(little) {
-                    Entry::Occupied(mut entry) => {
-                        let index = *entry.get();
-                        entry.insert(index + 1);
-                        index
-                    }
-                    Entry::Vacant(entry) => {
-                        let index = 1;
-                        entry.insert(index);
-                        index
-                    }
-                };
-                self.update_matching(
-                    &mut matching_set,
-                    little,
-                    self.little_preferences[little.index as usize]
-                        .iter()
-                        .skip(preference_index),
-                );
-            } else {
-                break;
+        self.find_even_matching_with_observer(&mut ())
+    }
+
+    /// Finds the evenly-distributed matching, reporting every step of the algorithm to
+    /// `observer`. See [`find_even_matching`](Self::find_even_matching) for more.
+    #[inline]
+    pub fn find_even_matching_with_observer<O>(&self, observer: &mut O) -> MatchingSet
+    where
+        O: Observer,
+    {
+        let mut solver = self.even_matching();
+        while solver.step_with_observer(observer).is_some() {}
+        solver.into_matching_set()
+    }
+
+    /// Finds a matching with the largest number of matched littles within the capacities of the
+    /// bigs, by matching littles along augmenting paths after the maximal matching. See
+    /// [`find_even_matching`](Self::find_even_matching) for more.
+    #[inline]
+    fn maximum_matching(&self) -> MatchingSet {
+        let mut matching_set = self.maximal_matching(&mut ());
+        while let Some((little, big, path)) = self.augmenting_path(&matching_set) {
+            for shift in path {
+                matching_set.shift(self, shift);
             }
+            matching_set.unmatched_littles.shift_remove(&little);
+            matching_set.insert_match(self, big, little);
         }
-        self.collect_unmatched_bigs(&mut matching_set);
         matching_set
     }
 
+    /// Returns a resumable solver for the evenly-distributed matching which can be run one step
+    /// at a time. See [`EvenMatching`] for more.
+    #[inline]
+    pub fn even_matching(&self) -> EvenMatching<'_> {
+        EvenMatching::new(self)
+    }
+
     /// Returns a [`Display`](fmt::Display) implementation for `self` which substitutes `names` for
     /// indices in the preference table.
     #[inline]
-    pub fn display<'s>(&'s self, names: &'s Names) -> PreferenceTableDisplay<'s> {
-        PreferenceTableDisplay { table: self, names }
+    pub fn display<'s, T>(&'s self, names: &'s Names<T>) -> PreferenceTableDisplay<'s, T>
+    where
+        T: fmt::Display,
+    {
+        PreferenceTableDisplay {
+            table: self,
+            names,
+            max_width: None,
+        }
+    }
+
+    /// Checks that every participant of `self` has a name in `names` and only ranks participants
+    /// who are in `self`, returning the first [`Inconsistency`] otherwise. The displays of a table
+    /// which fails this check still work, but show `#<index>` for the participants without a name.
+    #[inline]
+    pub fn check_against<T>(&self, names: &Names<T>) -> Result<(), Inconsistency> {
+        for (big, littles) in self.big_preferences.iter().enumerate() {
+            let big = BigIndex::from(big);
+            if names.get(big).is_none() {
+                return Err(Inconsistency::UnnamedBig(big));
+            }
+            if let Some(little) = littles
+                .iter()
+                .find(|l| l.index as usize >= self.little_preferences.len())
+            {
+                return Err(Inconsistency::UnknownLittle {
+                    big,
+                    little: *little,
+                });
+            }
+        }
+        for (little, bigs) in self.little_preferences.iter().enumerate() {
+            let little = LittleIndex::from(little);
+            if names.get(little).is_none() {
+                return Err(Inconsistency::UnnamedLittle(little));
+            }
+            if let Some(big) = bigs
+                .iter()
+                .find(|b| b.index as usize >= self.big_preferences.len())
+            {
+                return Err(Inconsistency::UnknownBig { little, big: *big });
+            }
+        }
+        Ok(())
     }
 }
 
@@ -463,7 +1075,7 @@ impl<K> SelectBase<K> for PreferenceTable
 where
     K: Kind,
 {
-    type Type = IndexSet<Vec<Index<<K as Kind>::Opposite>>>;
+    type Type = Vec<Vec<Index<<K as Kind>::Opposite>>>;
 }
 
 impl<K> Select<K> for PreferenceTable
@@ -496,50 +1108,289 @@ where
     }
 }
 
-/// Preference Table Display
-#[derive(Clone, Copy, Debug)]
-pub struct PreferenceTableDisplay<'s> {
+/// Even Matching Solver
+///
+/// Runs [`PreferenceTable::find_even_matching`] one step at a time. The first step computes the
+/// maximal matching and every following step shifts littles along one augmenting or cost-reducing
+/// path. After each step the intermediate [`MatchingSet`] is available from
+/// [`matching_set`](Self::matching_set), and as an [`Iterator`], this solver yields a copy of it
+/// after each step. The state after the last step is the same as the result of
+/// [`PreferenceTable::find_even_matching`].
+#[derive(Clone, Debug)]
+pub struct EvenMatching<'t> {
     /// Preference Table
-    table: &'s PreferenceTable,
+    table: &'t PreferenceTable,
 
-    /// Names
-    names: &'s Names,
+    /// Reduced Table of the Co-Big Units and Little Groups
+    reduced: Option<PreferenceTable>,
+
+    /// Current Matching Set of the Reduced Table
+    reduced_matching_set: MatchingSet,
+
+    /// Current Matching Set
+    matching_set: MatchingSet,
+
+    /// Step Count
+    steps: usize,
+
+    /// Finished Flag
+    finished: bool,
+}
+
+impl<'t> EvenMatching<'t> {
+    /// Builds a new [`EvenMatching`] solver over `table` which has not taken any steps yet.
+    #[inline]
+    pub fn new(table: &'t PreferenceTable) -> Self {
+        Self {
+            table,
+            reduced: table.reduced(),
+            reduced_matching_set: Default::default(),
+            matching_set: Default::default(),
+            steps: 0,
+            finished: false,
+        }
+    }
+
+    /// Returns the current state of the matching. Before the first step this is the empty
+    /// matching.
+    #[inline]
+    pub fn matching_set(&self) -> &MatchingSet {
+        &self.matching_set
+    }
+
+    /// Returns the number of steps taken so far, including the first step which computes the
+    /// maximal matching.
+    #[inline]
+    pub fn steps(&self) -> usize {
+        self.steps
+    }
+
+    /// Returns `true` if the solver has found the final matching and will not take any more
+    /// steps.
+    #[inline]
+    pub fn is_finished(&self) -> bool {
+        self.finished
+    }
+
+    /// Takes the next step of the algorithm, returning the new state of the matching, or `None` if
+    /// there are no steps left.
+    #[inline]
+    pub fn step(&mut self) -> Option<&MatchingSet> {
+        self.step_with_observer(&mut ())
+    }
+
+    /// Takes the next step of the algorithm, reporting it to `observer`, and returns the new
+    /// state of the matching, or `None` if there are no steps left. The final state of every
+    /// participant is reported on the call that returns `None`.
+    #[inline]
+    pub fn step_with_observer<O>(&mut self, observer: &mut O) -> Option<&MatchingSet>
+    where
+        O: Observer,
+    {
+        if self.finished {
+            return None;
+        }
+        let (table, matching_set) = match &self.reduced {
+            Some(reduced) => (reduced, &mut self.reduced_matching_set),
+            _ => (self.table, &mut self.matching_set),
+        };
+        let mut expanding = Expanding {
+            table: self.reduced.as_ref().map(|_| self.table),
+            observer,
+        };
+        if self.steps == 0 {
+            *matching_set = table.maximal_matching(&mut expanding);
+        } else if let Some((little, big, path)) = table.augmenting_path(matching_set) {
+            shift_with_observer(table, matching_set, path, &mut expanding);
+            expanding.observe(Event::Acceptance { little, big });
+            matching_set.unmatched_littles.shift_remove(&little);
+            matching_set.insert_match(table, big, little);
+        } else if let Some(path) = table.cost_reducing_path(matching_set) {
+            shift_with_observer(table, matching_set, path, &mut expanding);
+        } else {
+            self.finished = true;
+            PreferenceTable::observe_final(&self.matching_set, expanding.observer);
+            return None;
+        }
+        table.collect_unmatched_bigs(matching_set);
+        if self.reduced.is_some() {
+            self.matching_set = self.table.expand(self.reduced_matching_set.clone());
+        }
+        self.steps += 1;
+        Some(&self.matching_set)
+    }
+
+    /// Runs the solver to completion and returns the final matching.
+    #[inline]
+    pub fn finish(mut self) -> MatchingSet {
+        while self.step().is_some() {}
+        self.matching_set
+    }
+
+    /// Returns the current state of the matching, consuming the solver.
+    #[inline]
+    pub fn into_matching_set(self) -> MatchingSet {
+        self.matching_set
+    }
 }
 
-impl<'s> fmt::Display for PreferenceTableDisplay<'s> {
+/// Shifts every little along the `path` in `matching_set`, reporting each shift to `observer`.
+#[inline]
+fn shift_with_observer<O>(
+    table: &PreferenceTable,
+    matching_set: &mut MatchingSet,
+    path: Vec<Shift>,
+    observer: &mut O,
+) where
+    O: Observer,
+{
+    for shift in path {
+        observer.observe(Event::Shift {
+            little: shift.little,
+            from: shift.from,
+            to: shift.to,
+        });
+        matching_set.shift(table, shift);
+    }
+}
+
+impl<'t> Iterator for EvenMatching<'t> {
+    type Item = MatchingSet;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.step().cloned()
+    }
+}
+
+/// Inconsistency Error
+///
+/// Returned by [`PreferenceTable::check_against`] when a preference table does not match its
+/// [`Names`].
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Inconsistency {
+    /// Big in the Table without a Name
+    UnnamedBig(BigIndex),
+
+    /// Little in the Table without a Name
+    UnnamedLittle(LittleIndex),
+
+    /// Big Ranking a Little who is not in the Table
+    UnknownLittle {
+        /// Big Index
+        big: BigIndex,
+
+        /// Little Index
+        little: LittleIndex,
+    },
+
+    /// Little Ranking a Big who is not in the Table
+    UnknownBig {
+        /// Little Index
+        little: LittleIndex,
+
+        /// Big Index
+        big: BigIndex,
+    },
+}
+
+impl fmt::Display for Inconsistency {
     #[inline]
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        let longest_big_name_length = self.names.longest_name_length::<Big>();
-        let longest_little_name_length = self.names.longest_name_length::<Little>();
-        write!(f, "PreferenceTable {{\n    bigs: {{")?;
-        for (big, littles) in self.table.big_preferences.iter().enumerate() {
-            let big_name = self.names.get(BigIndex::from(big)).unwrap();
-            write!(
+        match self {
+            Self::UnnamedBig(big) => write!(f, "Big #{big:?} has no name."),
+            Self::UnnamedLittle(little) => write!(f, "Little #{little:?} has no name."),
+            Self::UnknownLittle { big, little } => write!(
                 f,
-                "\n        {}: {}[",
-                big_name,
-                " ".repeat(longest_big_name_length - big_name.len())
-            )?;
-            display_iter(f, littles.iter().map(|i| self.names.get(*i).unwrap()))?;
-            write!(f, "],")?;
+                "Big #{big:?} ranks little #{little:?} who is not in the table."
+            ),
+            Self::UnknownBig { little, big } => write!(
+                f,
+                "Little #{little:?} ranks big #{big:?} who is not in the table."
+            ),
         }
-        write!(f, "\n    }},\n    littles: {{")?;
-        for (little, bigs) in self.table.little_preferences.iter().enumerate() {
-            let little_name = self.names.get(LittleIndex::from(little)).unwrap();
-            write!(
+    }
+}
+
+#[cfg(feature = "std")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "std")))]
+impl std::error::Error for Inconsistency {}
+
+/// Preference Table Display
+#[derive(derivative::Derivative)]
+#[derivative(Debug(bound = "T: fmt::Debug"))]
+pub struct PreferenceTableDisplay<'s, T = String> {
+    /// Preference Table
+    table: &'s PreferenceTable,
+
+    /// Names
+    names: &'s Names<T>,
+
+    /// Maximum Name Width
+    max_width: Option<usize>,
+}
+
+impl<'s, T> PreferenceTableDisplay<'s, T> {
+    /// Truncates every name wider than `max_width` columns, ending it with an ellipsis.
+    #[inline]
+    pub fn max_width(mut self, max_width: usize) -> Self {
+        self.max_width = Some(max_width);
+        self
+    }
+}
+
+impl<'s, T> Clone for PreferenceTableDisplay<'s, T> {
+    #[inline]
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<'s, T> Copy for PreferenceTableDisplay<'s, T> {}
+
+impl<'s, T> PreferenceTableDisplay<'s, T>
+where
+    T: fmt::Display,
+{
+    /// Writes the preferences of every participant of kind `K` with their names aligned.
+    #[inline]
+    fn write_rows<K>(&self, f: &mut fmt::Formatter) -> fmt::Result
+    where
+        K: Kind,
+    {
+        let longest_name_length = self.names.longest_name_length::<K>(self.max_width);
+        for (index, preferences) in K::select(self.table).iter().enumerate() {
+            write!(f, "\n        ")?;
+            write_padded(
                 f,
-                "\n        {}: {}[",
-                little_name,
-                " ".repeat(longest_little_name_length - little_name.len())
+                &format_args!(
+                    "{}: ",
+                    Truncate(self.names.label(Index::<K>::from(index)), self.max_width)
+                ),
+                longest_name_length + 2,
+                Alignment::Left,
             )?;
-            display_iter(f, bigs.iter().map(|i| self.names.get(*i).unwrap()))?;
-            write!(f, "],")?;
+            write_names(f, self.names, preferences, self.max_width)?;
+            write!(f, ",")?;
         }
-        write!(f, "\n    }},\n}}")?;
         Ok(())
     }
 }
 
+impl<'s, T> fmt::Display for PreferenceTableDisplay<'s, T>
+where
+    T: fmt::Display,
+{
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "PreferenceTable {{\n    bigs: {{")?;
+        self.write_rows::<Big>(f)?;
+        write!(f, "\n    }},\n    littles: {{")?;
+        self.write_rows::<Little>(f)?;
+        write!(f, "\n    }},\n}}")
+    }
+}
+
 /// Matching
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct Matching {
@@ -548,6 +1399,14 @@ pub struct Matching {
 
     /// Little Indices
     pub littles: IndexSet<LittleIndex>,
+
+    /// Co-Big Indices
+    ///
+    /// The other bigs in the [co-big unit](PreferenceTable::pair_co_bigs) of `big`. The unit is
+    /// matched as a whole, and each of its littles is attributed to a member who ranks them back,
+    /// so the littles of the whole unit are those of `big` and of each of its co-bigs. A co-big
+    /// without any littles attributed to them is listed with the unmatched bigs.
+    pub co_bigs: IndexSet<BigIndex>,
 }
 
 impl Matching {
@@ -556,7 +1415,11 @@ impl Matching {
     pub fn from_pair(big: BigIndex, little: LittleIndex) -> Self {
         let mut littles = IndexSet::with_capacity(1);
         littles.insert(little);
-        Self { big, littles }
+        Self {
+            big,
+            littles,
+            co_bigs: IndexSet::new(),
+        }
     }
 
     /// Inserts `little` into the matching and sorts the matching according to the big's preferences
@@ -584,6 +1447,21 @@ impl Matching {
     }
 }
 
+/// Matching Shift
+///
+/// Moves `little` from the matching with the `from` big to the matching with the `to` big.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+struct Shift {
+    /// Little Index
+    little: LittleIndex,
+
+    /// Source Big Index
+    from: BigIndex,
+
+    /// Target Big Index
+    to: BigIndex,
+}
+
 /// Matching Set
 #[derive(Clone, Debug, Default, Eq, PartialEq)]
 pub struct MatchingSet {
@@ -598,6 +1476,68 @@ pub struct MatchingSet {
 }
 
 impl MatchingSet {
+    /// Builds a [`MatchingSet`] from its matched `pairs` and unmatched participants, like a
+    /// matching read back from a file. The littles of each big keep the order in which they
+    /// appear in `pairs`.
+    #[inline]
+    pub fn from_parts<P, B, L>(pairs: P, unmatched_bigs: B, unmatched_littles: L) -> Self
+    where
+        P: IntoIterator<Item = (BigIndex, LittleIndex)>,
+        B: IntoIterator<Item = BigIndex>,
+        L: IntoIterator<Item = LittleIndex>,
+    {
+        let mut matches = Vec::<Matching>::new();
+        for (big, little) in pairs {
+            match matches.binary_search_by_key(&big, |m| m.big) {
+                Ok(index) => {
+                    matches[index].littles.insert(little);
+                }
+                Err(index) => matches.insert(index, Matching::from_pair(big, little)),
+            }
+        }
+        Self {
+            matches,
+            unmatched_bigs: unmatched_bigs.into_iter().collect(),
+            unmatched_littles: unmatched_littles.into_iter().collect(),
+        }
+    }
+
+    /// Returns the matches in `self`, sorted by big index.
+    #[inline]
+    pub fn matches(&self) -> &[Matching] {
+        &self.matches
+    }
+
+    /// Returns an iterator over every matched pair in `self`, ordered by big index and then by
+    /// each big's preferences.
+    #[inline]
+    pub fn pairs(&self) -> impl Iterator<Item = (BigIndex, LittleIndex)> + '_ {
+        self.matches
+            .iter()
+            .flat_map(|m| m.littles.iter().map(move |l| (m.big, *l)))
+    }
+
+    /// Returns the matching for `big` if it was matched with any little.
+    #[inline]
+    pub fn matching(&self, big: BigIndex) -> Option<&Matching> {
+        self.matches
+            .binary_search_by_key(&big, |m| m.big)
+            .ok()
+            .map(|index| &self.matches[index])
+    }
+
+    /// Returns the bigs which were not matched with any little.
+    #[inline]
+    pub fn unmatched_bigs(&self) -> &IndexSet<BigIndex> {
+        &self.unmatched_bigs
+    }
+
+    /// Returns the littles which were not matched with any big.
+    #[inline]
+    pub fn unmatched_littles(&self) -> &IndexSet<LittleIndex> {
+        &self.unmatched_littles
+    }
+
     /// Inserts the `big`-`little` match into `self`, sorting the existing match by the preference
     /// `table` according to the `big`.
     #[inline]
@@ -608,119 +1548,113 @@ impl MatchingSet {
         }
     }
 
-    /// Finds the next largest matching in `self` which should remove its lowest ranking little.
-    /// This method is the exiting condition for the [`PreferenceTable::find_even_matching`] method.
-    ///
-    /// # Exit Conditions
-    ///
-    /// This method returns `None` on the following conditions (in this order):
-    ///
-    /// 1. The number of matches is equal to the number of bigs.
-    /// 2. There are no matches whatsoever.
-    /// 3. All the matches have the same number of littles.
+    /// Removes `little` from the matching with `big`, dropping the matching if it becomes empty.
     #[inline]
-    fn next_largest_match(&mut self, big_count: usize) -> Option<&mut Matching> {
-        if self.matches.len() == big_count {
-            return None;
-        }
-        if self.matches.len() == 1 {
-            return self.matches.get_mut(0);
-        }
-        let first_len = self.matches.first()?.littles.len();
-        if self
-            .matches
-            .iter()
-            .skip(1)
-            .all(|m| first_len == m.littles.len())
-        {
-            return None;
-        }
-        self.matches.iter_mut().reduce(|lhs, rhs| {
-            if lhs.littles.len() < rhs.littles.len() {
-                rhs
-            } else {
-                lhs
+    fn remove_match(&mut self, big: BigIndex, little: LittleIndex) {
+        if let Ok(index) = self.matches.binary_search_by_key(&big, |m| m.big) {
+            self.matches[index].littles.shift_remove(&little);
+            if self.matches[index].littles.is_empty() {
+                self.matches.remove(index);
             }
-        })
+        }
+    }
+
+    /// Moves the little of `shift` from the matching with its source big to the matching with its
+    /// target big.
+    #[inline]
+    fn shift(&mut self, table: &PreferenceTable, shift: Shift) {
+        self.remove_match(shift.from, shift.little);
+        self.insert_match(table, shift.to, shift.little);
     }
 
     /// Returns a [`Display`](fmt::Display) implementation for `self` which substitutes `names` for
     /// indices in the matching set.
     #[inline]
-    pub fn display<'s>(&'s self, names: &'s Names) -> MatchingSetDisplay<'s> {
+    pub fn display<'s, T>(&'s self, names: &'s Names<T>) -> MatchingSetDisplay<'s, T>
+    where
+        T: fmt::Display,
+    {
         MatchingSetDisplay {
             matching_set: self,
             names,
+            max_width: None,
         }
     }
 }
 
 /// Matching Set Display
-#[derive(Clone, Copy, Debug)]
-pub struct MatchingSetDisplay<'s> {
+#[derive(derivative::Derivative)]
+#[derivative(Debug(bound = "T: fmt::Debug"))]
+pub struct MatchingSetDisplay<'s, T = String> {
     /// Matching Set
     matching_set: &'s MatchingSet,
 
     /// Names
-    names: &'s Names,
+    names: &'s Names<T>,
+
+    /// Maximum Name Width
+    max_width: Option<usize>,
 }
 
-impl<'s> fmt::Display for MatchingSetDisplay<'s> {
+impl<'s, T> MatchingSetDisplay<'s, T> {
+    /// Truncates every name wider than `max_width` columns, ending it with an ellipsis.
+    #[inline]
+    pub fn max_width(mut self, max_width: usize) -> Self {
+        self.max_width = Some(max_width);
+        self
+    }
+}
+
+impl<'s, T> Clone for MatchingSetDisplay<'s, T> {
+    #[inline]
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<'s, T> Copy for MatchingSetDisplay<'s, T> {}
+
+impl<'s, T> fmt::Display for MatchingSetDisplay<'s, T>
+where
+    T: fmt::Display,
+{
     #[inline]
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        let longest_big_name_length = self.names.longest_name_length::<Big>();
+        let longest_big_name_length = self
+            .matching_set
+            .matches
+            .iter()
+            .map(|m| width(&UnitLabel(self.names, m, self.max_width)))
+            .fold(
+                self.names.longest_name_length::<Big>(self.max_width),
+                usize::max,
+            );
         write!(f, "MatchingSet {{\n    matches: {{")?;
         for matching in &self.matching_set.matches {
-            let big_name = self.names.get(matching.big).unwrap();
-            write!(
-                f,
-                "\n        {}: {}[",
-                big_name,
-                " ".repeat(longest_big_name_length - big_name.len())
-            )?;
-            display_iter(
+            write!(f, "\n        ")?;
+            write_padded(
                 f,
-                matching.littles.iter().map(|i| self.names.get(*i).unwrap()),
+                &format_args!("{}: ", UnitLabel(self.names, matching, self.max_width)),
+                longest_big_name_length + 2,
+                Alignment::Left,
             )?;
-            write!(f, "],")?;
+            write_names(f, self.names, &matching.littles, self.max_width)?;
+            write!(f, ",")?;
         }
-        writeln!(f, "\n    }},")?;
-        write!(f, "    unmatched_bigs:    [")?;
-        display_iter(
+        write!(f, "\n    }},\n    unmatched_bigs:    ")?;
+        write_names(
             f,
-            self.matching_set
-                .unmatched_bigs
-                .iter()
-                .map(|i| self.names.get(*i).unwrap()),
+            self.names,
+            &self.matching_set.unmatched_bigs,
+            self.max_width,
         )?;
-        writeln!(f, "]")?;
-        write!(f, "    unmatched_littles: [")?;
-        display_iter(
+        write!(f, "\n    unmatched_littles: ")?;
+        write_names(
             f,
-            self.matching_set
-                .unmatched_littles
-                .iter()
-                .map(|i| self.names.get(*i).unwrap()),
+            self.names,
+            &self.matching_set.unmatched_littles,
+            self.max_width,
         )?;
-        writeln!(f, "],")?;
-        write!(f, "}}")
-    }
-}
-
-/// Displays an iterator by adding commas between each element.
-#[inline]
-fn display_iter<'t, T, I>(f: &mut fmt::Formatter, iter: I) -> fmt::Result
-where
-    T: 't + fmt::Display,
-    I: IntoIterator<Item = &'t T>,
-{
-    let mut iter = iter.into_iter().peekable();
-    while let Some(next) = iter.next() {
-        if iter.peek().is_some() {
-            write!(f, "{}, ", next)?;
-        } else {
-            write!(f, "{}", next)?;
-        }
+        write!(f, ",\n}}")
     }
-    Ok(())
 }
@@ -0,0 +1,300 @@
+//! Family Lineage
+//!
+//! Every big-little pairing links two members of a family tree, and since littles become bigs in
+//! later seasons, the pairings of many seasons grow into lineages. A [`Lineage`] is keyed by
+//! participant name like the [`History`] it is usually built from, and can answer who the
+//! ancestors and descendants of a member are, group the members into families, feed the family
+//! sizes to the [even matching](PreferenceTable::find_even_matching), and be exported to
+//! Graphviz DOT.
+
+use crate::{dot::DotId, history::History, Big, Names, PreferenceTable};
+use alloc::{string::String, vec, vec::Vec};
+use core::{borrow::Borrow, fmt, hash::Hash};
+use indexmap::IndexSet;
+
+/// Family Lineage
+#[derive(derivative::Derivative)]
+#[derivative(
+    Clone(bound = "T: Clone"),
+    Debug(bound = "T: fmt::Debug"),
+    Default(bound = "")
+)]
+pub struct Lineage<T = String> {
+    /// Members
+    members: IndexSet<T>,
+
+    /// Bigs of each Member
+    bigs: Vec<IndexSet<usize>>,
+
+    /// Littles of each Member
+    littles: Vec<IndexSet<usize>>,
+}
+
+impl<T> Lineage<T>
+where
+    T: Eq + Hash,
+{
+    /// Builds the [`Lineage`] of every pairing in the seasons of `history`.
+    #[inline]
+    pub fn from_history(history: &History<T>) -> Self
+    where
+        T: Clone,
+    {
+        let mut lineage = Self::default();
+        for (big, little) in history.seasons().iter().flat_map(|s| &s.pairs) {
+            lineage.insert(big.clone(), little.clone());
+        }
+        lineage
+    }
+
+    /// Returns the position of `member`, adding them if they are new.
+    #[inline]
+    fn insert_member(&mut self, member: T) -> usize {
+        let (index, inserted) = self.members.insert_full(member);
+        if inserted {
+            self.bigs.push(IndexSet::new());
+            self.littles.push(IndexSet::new());
+        }
+        index
+    }
+
+    /// Inserts the pairing of `big` with `little`.
+    #[inline]
+    pub fn insert(&mut self, big: T, little: T) {
+        let big = self.insert_member(big);
+        let little = self.insert_member(little);
+        self.bigs[little].insert(big);
+        self.littles[big].insert(little);
+    }
+
+    /// Returns the members of the lineage in the order they first appeared.
+    #[inline]
+    pub fn members(&self) -> impl Iterator<Item = &T> {
+        self.members.iter()
+    }
+
+    /// Returns `true` if `member` is part of the lineage.
+    #[inline]
+    pub fn contains<Q>(&self, member: &Q) -> bool
+    where
+        T: Borrow<Q>,
+        Q: Eq + Hash + ?Sized,
+    {
+        self.members.contains(member)
+    }
+
+    /// Returns the members reached from `member` by repeatedly following `edges`, in
+    /// breadth-first order and without `member` themself.
+    #[inline]
+    fn reachable<Q>(&self, member: &Q, edges: &[IndexSet<usize>]) -> Vec<&T>
+    where
+        T: Borrow<Q>,
+        Q: Eq + Hash + ?Sized,
+    {
+        let start = match self.members.get_index_of(member) {
+            Some(start) => start,
+            _ => return Vec::new(),
+        };
+        let mut seen = IndexSet::new();
+        seen.insert(start);
+        let mut cursor = 0;
+        while let Some(next) = seen.get_index(cursor).copied() {
+            cursor += 1;
+            seen.extend(edges[next].iter().copied());
+        }
+        seen.into_iter().skip(1).map(|i| &self.members[i]).collect()
+    }
+
+    /// Returns the bigs of `member`.
+    #[inline]
+    pub fn bigs<Q>(&self, member: &Q) -> Vec<&T>
+    where
+        T: Borrow<Q>,
+        Q: Eq + Hash + ?Sized,
+    {
+        self.neighbors(member, &self.bigs)
+    }
+
+    /// Returns the littles of `member`.
+    #[inline]
+    pub fn littles<Q>(&self, member: &Q) -> Vec<&T>
+    where
+        T: Borrow<Q>,
+        Q: Eq + Hash + ?Sized,
+    {
+        self.neighbors(member, &self.littles)
+    }
+
+    /// Returns the members linked to `member` by one of the `edges`.
+    #[inline]
+    fn neighbors<Q>(&self, member: &Q, edges: &[IndexSet<usize>]) -> Vec<&T>
+    where
+        T: Borrow<Q>,
+        Q: Eq + Hash + ?Sized,
+    {
+        self.members
+            .get_index_of(member)
+            .map(|i| edges[i].iter().map(|j| &self.members[*j]).collect())
+            .unwrap_or_default()
+    }
+
+    /// Returns the bigs of `member`, their bigs, and so on, nearest first.
+    #[inline]
+    pub fn ancestors<Q>(&self, member: &Q) -> Vec<&T>
+    where
+        T: Borrow<Q>,
+        Q: Eq + Hash + ?Sized,
+    {
+        self.reachable(member, &self.bigs)
+    }
+
+    /// Returns the littles of `member`, their littles, and so on, nearest first.
+    #[inline]
+    pub fn descendants<Q>(&self, member: &Q) -> Vec<&T>
+    where
+        T: Borrow<Q>,
+        Q: Eq + Hash + ?Sized,
+    {
+        self.reachable(member, &self.littles)
+    }
+
+    /// Returns the family of every member, numbering the families in the order their first member
+    /// appeared. Two members are in the same family if they are linked by a chain of pairings.
+    #[inline]
+    fn family_ids(&self) -> Vec<usize> {
+        let mut ids = vec![usize::MAX; self.members.len()];
+        let mut count = 0;
+        for start in 0..self.members.len() {
+            if ids[start] != usize::MAX {
+                continue;
+            }
+            ids[start] = count;
+            let mut stack = vec![start];
+            while let Some(member) = stack.pop() {
+                for next in self.bigs[member].iter().chain(&self.littles[member]) {
+                    if ids[*next] == usize::MAX {
+                        ids[*next] = count;
+                        stack.push(*next);
+                    }
+                }
+            }
+            count += 1;
+        }
+        ids
+    }
+
+    /// Returns every family with its members in the order they first appeared.
+    #[inline]
+    pub fn families(&self) -> Vec<Vec<&T>> {
+        let mut families = Vec::<Vec<&T>>::new();
+        for (member, id) in self.members.iter().zip(self.family_ids()) {
+            if id == families.len() {
+                families.push(Vec::new());
+            }
+            families[id].push(member);
+        }
+        families
+    }
+
+    /// Returns every member of the family of `member`, including `member`.
+    #[inline]
+    pub fn family<Q>(&self, member: &Q) -> Vec<&T>
+    where
+        T: Borrow<Q>,
+        Q: Eq + Hash + ?Sized,
+    {
+        match self.members.get_index_of(member) {
+            Some(index) => {
+                let ids = self.family_ids();
+                self.members
+                    .iter()
+                    .zip(&ids)
+                    .filter(|(_, id)| **id == ids[index])
+                    .map(|(m, _)| m)
+                    .collect()
+            }
+            _ => Vec::new(),
+        }
+    }
+
+    /// Puts every big of `table` into their family from `self`, looking them up by their `names`,
+    /// so that the [even matching](PreferenceTable::find_even_matching) balances the family sizes.
+    /// Bigs who are not part of the lineage yet start a family of their own.
+    #[inline]
+    pub fn apply(&self, table: &mut PreferenceTable, names: &Names<T>) {
+        let ids = self.family_ids();
+        let mut sizes = Vec::<usize>::new();
+        for id in &ids {
+            if *id == sizes.len() {
+                sizes.push(0);
+            }
+            sizes[*id] += 1;
+        }
+        for (id, size) in sizes.iter().enumerate() {
+            table.set_family_size(id, *size);
+        }
+        let mut next = sizes.len();
+        for (big, name) in names.iter::<Big>() {
+            match self.members.get_index_of(name) {
+                Some(index) => table.set_family(big, ids[index]),
+                _ => {
+                    table.set_family(big, next);
+                    table.set_family_size(next, 1);
+                    next += 1;
+                }
+            }
+        }
+    }
+
+    /// Returns a [`Display`](fmt::Display) implementation for `self` which writes the lineage as a
+    /// Graphviz DOT graph with an edge from every big to each of their littles.
+    #[inline]
+    pub fn dot(&self) -> LineageDot<'_, T>
+    where
+        T: fmt::Display,
+    {
+        LineageDot { lineage: self }
+    }
+}
+
+/// Lineage DOT Display
+#[derive(derivative::Derivative)]
+#[derivative(Debug(bound = "T: fmt::Debug"))]
+pub struct LineageDot<'l, T = String> {
+    /// Lineage
+    lineage: &'l Lineage<T>,
+}
+
+impl<'l, T> Clone for LineageDot<'l, T> {
+    #[inline]
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<'l, T> Copy for LineageDot<'l, T> {}
+
+impl<'l, T> fmt::Display for LineageDot<'l, T>
+where
+    T: fmt::Display,
+{
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let members = &self.lineage.members;
+        writeln!(f, "digraph lineage {{")?;
+        for member in members {
+            writeln!(f, "    {};", DotId(member))?;
+        }
+        for (big, littles) in self.lineage.littles.iter().enumerate() {
+            for little in littles {
+                writeln!(
+                    f,
+                    "    {} -> {};",
+                    DotId(&members[big]),
+                    DotId(&members[*little])
+                )?;
+            }
+        }
+        write!(f, "}}")
+    }
+}
@@ -0,0 +1,146 @@
+//! Participant Metadata
+//!
+//! Stores extra fields about each participant, like their email or pledge class, keyed by their
+//! [`Index`]. Metadata does not affect the matching on its own, but it can be carried through to
+//! any output and used to build constraints.
+
+use crate::{Big, Index, Kind, Little, Select, SelectBase, SelectMut, SelectType};
+use alloc::string::String;
+use indexmap::{IndexMap, IndexSet};
+
+/// Metadata Table
+///
+/// Stores the metadata fields for every participant of kind `K`.
+#[derive(derivative::Derivative)]
+#[derivative(Clone, Debug, Default(bound = ""), Eq, PartialEq)]
+pub struct MetadataTable<K>
+where
+    K: Kind,
+{
+    /// Field Names
+    fields: IndexSet<String>,
+
+    /// Records
+    records: IndexMap<Index<K>, IndexMap<String, String>>,
+}
+
+impl<K> MetadataTable<K>
+where
+    K: Kind,
+{
+    /// Returns all the field names used by any participant, in the order they were first seen.
+    #[inline]
+    pub fn fields(&self) -> &IndexSet<String> {
+        &self.fields
+    }
+
+    /// Inserts the `value` for `field` into the record of the participant at `index`, returning
+    /// the previous value if there was one.
+    #[inline]
+    pub fn insert(&mut self, index: Index<K>, field: String, value: String) -> Option<String> {
+        self.fields.insert(field.clone());
+        self.records.entry(index).or_default().insert(field, value)
+    }
+
+    /// Returns the value of `field` for the participant at `index`.
+    #[inline]
+    pub fn get(&self, index: Index<K>, field: &str) -> Option<&str> {
+        self.records
+            .get(&index)
+            .and_then(|r| r.get(field))
+            .map(String::as_str)
+    }
+
+    /// Returns the full record for the participant at `index`.
+    #[inline]
+    pub fn record(&self, index: Index<K>) -> Option<&IndexMap<String, String>> {
+        self.records.get(&index)
+    }
+}
+
+/// Participant Metadata
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Metadata {
+    /// Big Metadata
+    bigs: MetadataTable<Big>,
+
+    /// Little Metadata
+    littles: MetadataTable<Little>,
+}
+
+impl Metadata {
+    /// Returns all the field names used by any participant of kind `K`, in the order they were
+    /// first seen.
+    #[inline]
+    pub fn fields<K>(&self) -> &IndexSet<String>
+    where
+        K: Kind,
+    {
+        K::select(self).fields()
+    }
+
+    /// Inserts the `value` for `field` into the record of the participant at `index`, returning
+    /// the previous value if there was one.
+    #[inline]
+    pub fn insert<K>(&mut self, index: Index<K>, field: String, value: String) -> Option<String>
+    where
+        K: Kind,
+    {
+        K::select_mut(self).insert(index, field, value)
+    }
+
+    /// Returns the value of `field` for the participant at `index`.
+    #[inline]
+    pub fn get<K>(&self, index: Index<K>, field: &str) -> Option<&str>
+    where
+        K: Kind,
+    {
+        K::select(self).get(index, field)
+    }
+
+    /// Returns the full record for the participant at `index`.
+    #[inline]
+    pub fn record<K>(&self, index: Index<K>) -> Option<&IndexMap<String, String>>
+    where
+        K: Kind,
+    {
+        K::select(self).record(index)
+    }
+}
+
+impl<K> SelectBase<K> for Metadata
+where
+    K: Kind,
+{
+    type Type = MetadataTable<K>;
+}
+
+impl<K> Select<K> for Metadata
+where
+    K: Kind,
+{
+    #[inline]
+    fn big(&self) -> &SelectType<Big, Self> {
+        &self.bigs
+    }
+
+    #[inline]
+    fn little(&self) -> &SelectType<Little, Self> {
+        &self.littles
+    }
+}
+
+impl<K> SelectMut<K> for Metadata
+where
+    K: Kind,
+{
+    #[inline]
+    fn big(&mut self) -> &mut SelectType<Big, Self> {
+        &mut self.bigs
+    }
+
+    #[inline]
+    fn little(&mut self) -> &mut SelectType<Little, Self> {
+        &mut self.littles
+    }
+}
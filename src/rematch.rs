@@ -0,0 +1,157 @@
+//! Incremental Re-Matching
+//!
+//! Updates a matching after the [`PreferenceTable`] changed, like when a participant drops out
+//! and is [withdrawn](PreferenceTable::withdraw), a late participant is
+//! [inserted](PreferenceTable::insert), or someone [edits](PreferenceTable::set_preferences)
+//! their preferences. Instead of solving from scratch, the previous matching is kept wherever it
+//! is still valid, so that only the pairs affected by the change are moved.
+
+use crate::{diff::MatchingDiff, BigIndex, LittleIndex, MatchingSet, PreferenceTable};
+
+/// Re-Matching
+///
+/// The updated [`MatchingSet`] together with the [`MatchingDiff`] from the previous one.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Rematching {
+    /// Updated Matching Set
+    matching_set: MatchingSet,
+
+    /// Changes from the Previous Matching Set
+    diff: MatchingDiff,
+}
+
+impl Rematching {
+    /// Updates the `previous` matching after a change to `table`.
+    ///
+    /// # Algorithm
+    ///
+    /// First every [locked](PreferenceTable::lock) pair is matched, and every other pair of
+    /// `previous` is kept if the big and the little still rank each other and the big still has
+    /// room, keeping the littles the big ranks highest if their
+    /// [capacity](PreferenceTable::set_capacity) went down. Then every unmatched little is placed
+    /// with the mutually-acceptable big with room that has the fewest littles, breaking ties by
+    /// the preferences of the little, which moves nobody else. Finally the littles who are still
+    /// unmatched are matched along the shortest augmenting paths, which move as few littles as
+    /// possible. Like [`find_even_matching`](PreferenceTable::find_even_matching), the result
+    /// matches the largest possible number of littles, but it is not rebalanced along
+    /// cost-reducing paths, since that would move pairs which are still valid. With co-big units
+    /// and little groups, the previous matching is kept and updated for whole units and groups,
+    /// as described in the [`group`](crate::group) module.
+    #[inline]
+    pub fn new(table: &PreferenceTable, previous: &MatchingSet) -> Self {
+        let matching_set = match table.reduced() {
+            Some(reduced) => table.expand(update(&reduced, &previous.reduced(table))),
+            _ => update(table, previous),
+        };
+        Self {
+            diff: previous.diff_ignoring_order(&matching_set),
+            matching_set,
+        }
+    }
+
+    /// Returns the updated matching.
+    #[inline]
+    pub fn matching_set(&self) -> &MatchingSet {
+        &self.matching_set
+    }
+
+    /// Returns the updated matching, consuming `self`.
+    #[inline]
+    pub fn into_matching_set(self) -> MatchingSet {
+        self.matching_set
+    }
+
+    /// Returns the changes from the previous matching to the updated one: the littles who were
+    /// added, removed, or moved to another big, and who entered or left the unmatched sets. Since
+    /// the littles of each big are sorted by their preferences again, changes in their order are
+    /// not reported.
+    #[inline]
+    pub fn diff(&self) -> &MatchingDiff {
+        &self.diff
+    }
+
+    /// Returns `true` if the updated matching has exactly the same pairs as the previous one.
+    #[inline]
+    pub fn is_unchanged(&self) -> bool {
+        self.diff.added().is_empty()
+            && self.diff.removed().is_empty()
+            && self.diff.moved().is_empty()
+    }
+}
+
+/// Updates the `previous` matching after a change to `table`. See [`Rematching::new`] for more.
+#[inline]
+fn update(table: &PreferenceTable, previous: &MatchingSet) -> MatchingSet {
+    let mut matching_set = MatchingSet::default();
+    table.insert_locks(&mut matching_set, &mut ());
+    for matching in previous.matches() {
+        if table.big_preferences.len() <= matching.big.index as usize {
+            continue;
+        }
+        let mut matching = matching.clone();
+        matching.sort(table);
+        for little in matching.littles {
+            if !table.is_locked(little)
+                && is_mutual(table, matching.big, little)
+                && table.has_room(
+                    matching.big,
+                    table.load(&matching_set, matching.big),
+                    table.weight(little),
+                )
+            {
+                matching_set.insert_match(table, matching.big, little);
+            }
+        }
+    }
+    for little in 0..table.little_preferences.len() {
+        let little = LittleIndex::from(little);
+        if !table.is_follower(little) && !matching_set.pairs().any(|(_, l)| l == little) {
+            matching_set.unmatched_littles.insert(little);
+        }
+    }
+    for little in matching_set.unmatched_littles.clone() {
+        let big = table.little_preferences[little.index as usize]
+            .iter()
+            .copied()
+            .filter(|big| {
+                big.preference(little, table).is_some()
+                    && table.has_room(*big, table.load(&matching_set, *big), table.weight(little))
+            })
+            .min_by_key(|big| table.load(&matching_set, *big));
+        if let Some(big) = big {
+            matching_set.unmatched_littles.shift_remove(&little);
+            matching_set.insert_match(table, big, little);
+        }
+    }
+    while let Some((little, big, path)) = table.augmenting_path(&matching_set) {
+        for shift in path {
+            matching_set.shift(table, shift);
+        }
+        matching_set.unmatched_littles.shift_remove(&little);
+        matching_set.insert_match(table, big, little);
+    }
+    table.collect_unmatched_bigs(&mut matching_set);
+    matching_set
+}
+
+/// Returns `true` if `big` and `little` are both in `table` and rank each other.
+#[inline]
+fn is_mutual(table: &PreferenceTable, big: BigIndex, little: LittleIndex) -> bool {
+    table
+        .big_preferences
+        .get(big.index as usize)
+        .is_some_and(|row| row.contains(&little))
+        && table
+            .little_preferences
+            .get(little.index as usize)
+            .is_some_and(|row| row.contains(&big))
+}
+
+impl PreferenceTable {
+    /// Updates the `previous` matching after a change to `self`, keeping its pairs wherever
+    /// possible. See [`Rematching::new`] for more.
+    #[inline]
+    pub fn rematch(&self, previous: &MatchingSet) -> Rematching {
+        Rematching::new(self, previous)
+    }
+}
@@ -0,0 +1,559 @@
+//! Table Rendering
+//!
+//! The shared layer under every display of this crate: measuring names, padding them into
+//! columns, and listing them with commas. On top of it, a [`Table`] of named columns can be laid
+//! out as an aligned ASCII table or as a GitHub Markdown table, which paste well into terminals,
+//! issues, and documents. See [`PreferenceTable::table`] and [`MatchingSet::table`] for the tables
+//! of preferences and matchings.
+
+use crate::{Index, Kind, Matching, MatchingSet, Names, PreferenceTable};
+use alloc::{
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::fmt::{self, Write};
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
+
+/// Table Style
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Deserialize, serde::Serialize),
+    serde(rename_all = "kebab-case")
+)]
+pub enum TableStyle {
+    /// Aligned ASCII Table with Borders
+    #[default]
+    Ascii,
+
+    /// GitHub Markdown Table
+    Markdown,
+}
+
+/// Column Alignment
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub enum Alignment {
+    /// Left-Aligned Column
+    #[default]
+    Left,
+
+    /// Right-Aligned Column
+    Right,
+}
+
+/// Table
+///
+/// A list of rows of rendered cells under named columns.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Table {
+    /// Column Headers and Alignments
+    columns: Vec<(String, Alignment)>,
+
+    /// Rows
+    rows: Vec<Vec<String>>,
+}
+
+impl Table {
+    /// Builds a new empty [`Table`] with `columns` of headers and alignments.
+    #[inline]
+    pub fn new<I, S>(columns: I) -> Self
+    where
+        I: IntoIterator<Item = (S, Alignment)>,
+        S: Into<String>,
+    {
+        Self {
+            columns: columns
+                .into_iter()
+                .map(|(header, alignment)| (header.into(), alignment))
+                .collect(),
+            rows: Vec::new(),
+        }
+    }
+
+    /// Appends a row of `cells`, leaving the missing cells empty and dropping the cells which do
+    /// not have a column.
+    #[inline]
+    pub fn push_row<I>(&mut self, cells: I)
+    where
+        I: IntoIterator,
+        I::Item: fmt::Display,
+    {
+        let mut row = cells
+            .into_iter()
+            .take(self.columns.len())
+            .map(|cell| cell.to_string())
+            .collect::<Vec<_>>();
+        row.resize(self.columns.len(), String::new());
+        self.rows.push(row);
+    }
+
+    /// Returns the headers of the columns.
+    #[inline]
+    pub fn headers(&self) -> impl Iterator<Item = &str> {
+        self.columns.iter().map(|(header, _)| header.as_str())
+    }
+
+    /// Returns the rows of the table.
+    #[inline]
+    pub fn rows(&self) -> &[Vec<String>] {
+        &self.rows
+    }
+
+    /// Returns a [`Display`](fmt::Display) implementation for `self` which lays out the table in
+    /// the given `style`.
+    #[inline]
+    pub fn display(&self, style: TableStyle) -> TableDisplay<'_> {
+        TableDisplay {
+            table: self,
+            style,
+            max_width: None,
+        }
+    }
+}
+
+/// Table Display
+#[derive(Clone, Copy, Debug)]
+pub struct TableDisplay<'t> {
+    /// Table
+    table: &'t Table,
+
+    /// Table Style
+    style: TableStyle,
+
+    /// Maximum Cell Width
+    max_width: Option<usize>,
+}
+
+impl TableDisplay<'_> {
+    /// Truncates every cell below the headers wider than `max_width` columns, ending it with an
+    /// ellipsis.
+    #[inline]
+    pub fn max_width(mut self, max_width: usize) -> Self {
+        self.max_width = Some(max_width);
+        self
+    }
+
+    /// Returns the width of the widest cell of every column, including its header.
+    #[inline]
+    fn widths(&self) -> Vec<usize> {
+        let minimum = match self.style {
+            TableStyle::Ascii => 0,
+            TableStyle::Markdown => 3,
+        };
+        self.table
+            .columns
+            .iter()
+            .enumerate()
+            .map(|(column, (header, _))| {
+                self.table
+                    .rows
+                    .iter()
+                    .map(|row| width(&self.cell(&row[column])))
+                    .fold(
+                        width(&Cell(header, self.style, None)).max(minimum),
+                        usize::max,
+                    )
+            })
+            .collect()
+    }
+
+    /// Returns the truncated and escaped text of `cell`.
+    #[inline]
+    fn cell<'c>(&self, cell: &'c str) -> Cell<'c> {
+        Cell(cell, self.style, self.max_width)
+    }
+
+    /// Writes one row of `cells` padded to `widths`, truncating them unless they are `headers`.
+    #[inline]
+    fn write_row<'c, I>(
+        &self,
+        f: &mut fmt::Formatter,
+        widths: &[usize],
+        cells: I,
+        headers: bool,
+    ) -> fmt::Result
+    where
+        I: IntoIterator<Item = &'c String>,
+    {
+        for ((cell, width), (_, alignment)) in
+            cells.into_iter().zip(widths).zip(&self.table.columns)
+        {
+            let cell = if headers {
+                Cell(cell, self.style, None)
+            } else {
+                self.cell(cell)
+            };
+            write!(f, "| ")?;
+            write_padded(f, &cell, *width, *alignment)?;
+            write!(f, " ")?;
+        }
+        write!(f, "|")
+    }
+
+    /// Writes a rule between the rows, which is a border for ASCII tables and the delimiter row for
+    /// Markdown tables.
+    #[inline]
+    fn write_rule(&self, f: &mut fmt::Formatter, widths: &[usize]) -> fmt::Result {
+        for (width, (_, alignment)) in widths.iter().zip(&self.table.columns) {
+            match (self.style, alignment) {
+                (TableStyle::Ascii, _) => write!(f, "+-{}-", Repeat('-', *width))?,
+                (TableStyle::Markdown, Alignment::Left) => write!(f, "| {} ", Repeat('-', *width))?,
+                (TableStyle::Markdown, Alignment::Right) => {
+                    write!(f, "| {}: ", Repeat('-', width - 1))?
+                }
+            }
+        }
+        match self.style {
+            TableStyle::Ascii => write!(f, "+"),
+            TableStyle::Markdown => write!(f, "|"),
+        }
+    }
+}
+
+impl fmt::Display for TableDisplay<'_> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let widths = self.widths();
+        let headers = self.table.columns.iter().map(|(header, _)| header);
+        if self.style == TableStyle::Ascii {
+            self.write_rule(f, &widths)?;
+            writeln!(f)?;
+        }
+        self.write_row(f, &widths, headers, true)?;
+        writeln!(f)?;
+        self.write_rule(f, &widths)?;
+        for row in &self.table.rows {
+            writeln!(f)?;
+            self.write_row(f, &widths, row, false)?;
+        }
+        if self.style == TableStyle::Ascii {
+            writeln!(f)?;
+            self.write_rule(f, &widths)?;
+        }
+        Ok(())
+    }
+}
+
+/// Table Cell
+///
+/// Writes the text of a cell truncated to the maximum width, escaping pipes in Markdown tables.
+struct Cell<'c>(&'c str, TableStyle, Option<usize>);
+
+impl fmt::Display for Cell<'_> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let text = Truncate(self.0, self.2);
+        match self.1 {
+            TableStyle::Ascii => write!(f, "{text}"),
+            TableStyle::Markdown => write!(Escape(f), "{text}"),
+        }
+    }
+}
+
+/// Markdown Escaping Writer
+struct Escape<'f, 'g>(&'f mut fmt::Formatter<'g>);
+
+impl Write for Escape<'_, '_> {
+    #[inline]
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        for (i, part) in s.split('|').enumerate() {
+            if i > 0 {
+                self.0.write_str("\\|")?;
+            }
+            self.0.write_str(part)?;
+        }
+        Ok(())
+    }
+}
+
+impl PreferenceTable {
+    /// Returns a [`Table`] of the preferences of every participant of kind `K`, substituting
+    /// `names` for indices, with one column for each rank.
+    #[inline]
+    pub fn table<K, T>(&self, names: &Names<T>) -> Table
+    where
+        K: Kind,
+        T: fmt::Display,
+    {
+        let rows = K::select(self);
+        let ranks = rows.iter().map(Vec::len).max().unwrap_or_default();
+        let mut table = Table::new(
+            [(K::dynamic().to_string(), Alignment::Left)]
+                .into_iter()
+                .chain((1..=ranks).map(|rank| (alloc::format!("Rank {rank}"), Alignment::Left))),
+        );
+        for (index, row) in rows.iter().enumerate() {
+            table.push_row(
+                [names.label(Index::<K>::from(index)).to_string()]
+                    .into_iter()
+                    .chain(row.iter().map(|i| names.label(*i).to_string())),
+            );
+        }
+        table
+    }
+}
+
+impl MatchingSet {
+    /// Returns a [`Table`] of every pair in `self` with the rank each side gave the other in
+    /// `table`, followed by a row for each unmatched participant, substituting `names` for
+    /// indices.
+    #[inline]
+    pub fn table<T>(&self, table: &PreferenceTable, names: &Names<T>) -> Table
+    where
+        T: fmt::Display,
+    {
+        let mut output = Table::new([
+            ("Big", Alignment::Left),
+            ("Little", Alignment::Left),
+            ("Rank by Big", Alignment::Right),
+            ("Rank by Little", Alignment::Right),
+        ]);
+        for matching in &self.matches {
+            let big = matching.big;
+            for little in &matching.littles {
+                output.push_row([
+                    UnitLabel(names, matching, None).to_string(),
+                    names.label(*little).to_string(),
+                    Rank(rank(table, big, *little)).to_string(),
+                    Rank(rank(table, *little, big)).to_string(),
+                ]);
+            }
+        }
+        for big in &self.unmatched_bigs {
+            output.push_row([names.label(*big).to_string()]);
+        }
+        for little in &self.unmatched_littles {
+            output.push_row([String::new(), names.label(*little).to_string()]);
+        }
+        output
+    }
+}
+
+/// Returns the one-based rank of `other` in the preferences of `index` in `table`, if it is
+/// ranked.
+#[inline]
+pub(crate) fn rank<K>(
+    table: &PreferenceTable,
+    index: Index<K>,
+    other: Index<K::Opposite>,
+) -> Option<usize>
+where
+    K: Kind,
+{
+    index
+        .preference(other, table)
+        .map(|p| p.preference.get() as usize)
+}
+
+/// Participant Label
+///
+/// Writes the name of a participant, or `#<index>` if there is no name for their index, so that
+/// tables which do not match their [`Names`] can still be displayed.
+#[derive(derivative::Derivative)]
+#[derivative(Clone(bound = ""), Copy(bound = ""), Debug(bound = "T: fmt::Debug"))]
+pub enum Label<'n, T> {
+    /// Known Name
+    Name(&'n T),
+
+    /// Unknown Index
+    Unknown(u32),
+}
+
+impl<T> fmt::Display for Label<'_, T>
+where
+    T: fmt::Display,
+{
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Name(name) => name.fmt(f),
+            Self::Unknown(index) => write!(f, "#{index}"),
+        }
+    }
+}
+
+/// Co-Big Unit Label
+///
+/// Writes the name of the big of a [`Matching`] followed by the names of its
+/// [co-bigs](Matching::co_bigs), joined by ` & ` and each truncated to the maximum width.
+pub(crate) struct UnitLabel<'s, T>(pub &'s Names<T>, pub &'s Matching, pub Option<usize>);
+
+impl<T> fmt::Display for UnitLabel<'_, T>
+where
+    T: fmt::Display,
+{
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", Truncate(self.0.label(self.1.big), self.2))?;
+        for co_big in &self.1.co_bigs {
+            write!(f, " & {}", Truncate(self.0.label(*co_big), self.2))?;
+        }
+        Ok(())
+    }
+}
+
+/// Rank Cell
+///
+/// Writes a one-based rank, or `-` if there is none.
+pub(crate) struct Rank(pub Option<usize>);
+
+impl fmt::Display for Rank {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.0 {
+            Some(rank) => write!(f, "{rank}"),
+            _ => f.write_str("-"),
+        }
+    }
+}
+
+/// Repeated Character
+struct Repeat(char, usize);
+
+impl fmt::Display for Repeat {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for _ in 0..self.1 {
+            f.write_char(self.0)?;
+        }
+        Ok(())
+    }
+}
+
+/// Returns the terminal display width of the [`Display`](fmt::Display) representation of `value`,
+/// counting wide characters like CJK ideographs as two columns and combining marks as none.
+#[inline]
+pub fn width<T>(value: &T) -> usize
+where
+    T: fmt::Display + ?Sized,
+{
+    /// Width Counter
+    struct Counter(usize);
+
+    impl Write for Counter {
+        #[inline]
+        fn write_str(&mut self, s: &str) -> fmt::Result {
+            self.0 += UnicodeWidthStr::width(s);
+            Ok(())
+        }
+    }
+
+    let mut counter = Counter(0);
+    let _ = write!(counter, "{}", value);
+    counter.0
+}
+
+/// Truncated Text
+///
+/// Writes a value cut down to at most `max_width` columns, ending with an ellipsis if it was cut.
+pub(crate) struct Truncate<T>(pub T, pub Option<usize>);
+
+impl<T> fmt::Display for Truncate<T>
+where
+    T: fmt::Display,
+{
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        /// Width-Limited Writer
+        struct Limit<'f, 'g> {
+            /// Formatter
+            f: &'f mut fmt::Formatter<'g>,
+
+            /// Remaining Width
+            remaining: usize,
+
+            /// Cut Flag
+            cut: bool,
+        }
+
+        impl Write for Limit<'_, '_> {
+            #[inline]
+            fn write_str(&mut self, s: &str) -> fmt::Result {
+                for c in s.chars() {
+                    let width = UnicodeWidthChar::width(c).unwrap_or_default();
+                    if self.cut || width > self.remaining {
+                        self.cut = true;
+                        return Ok(());
+                    }
+                    self.remaining -= width;
+                    self.f.write_char(c)?;
+                }
+                Ok(())
+            }
+        }
+
+        match self.1 {
+            Some(max_width) if width(&self.0) > max_width => {
+                if max_width == 0 {
+                    return Ok(());
+                }
+                let mut limit = Limit {
+                    f,
+                    remaining: max_width - 1,
+                    cut: false,
+                };
+                write!(limit, "{}", self.0)?;
+                f.write_char('…')
+            }
+            _ => write!(f, "{}", self.0),
+        }
+    }
+}
+
+/// Writes `value` padded with spaces to `width` according to `alignment`.
+#[inline]
+pub(crate) fn write_padded<T>(
+    f: &mut fmt::Formatter,
+    value: &T,
+    width: usize,
+    alignment: Alignment,
+) -> fmt::Result
+where
+    T: fmt::Display + ?Sized,
+{
+    let padding = Repeat(' ', width.saturating_sub(self::width(value)));
+    match alignment {
+        Alignment::Left => write!(f, "{value}{padding}"),
+        Alignment::Right => write!(f, "{padding}{value}"),
+    }
+}
+
+/// Writes the items of `iter` separated by commas.
+#[inline]
+pub(crate) fn write_list<T, I>(f: &mut fmt::Formatter, iter: I) -> fmt::Result
+where
+    T: fmt::Display,
+    I: IntoIterator<Item = T>,
+{
+    for (i, next) in iter.into_iter().enumerate() {
+        if i > 0 {
+            write!(f, ", ")?;
+        }
+        write!(f, "{next}")?;
+    }
+    Ok(())
+}
+
+/// Writes the names of a list of participants of kind `K` as `[a, b, c]`, truncating each name to
+/// `max_width` if there is one.
+#[inline]
+pub(crate) fn write_names<'i, K, T, I>(
+    f: &mut fmt::Formatter,
+    names: &Names<T>,
+    indices: I,
+    max_width: Option<usize>,
+) -> fmt::Result
+where
+    K: Kind,
+    T: fmt::Display,
+    I: IntoIterator<Item = &'i Index<K>>,
+{
+    write!(f, "[")?;
+    write_list(
+        f,
+        indices
+            .into_iter()
+            .map(|i| Truncate(names.label(*i), max_width)),
+    )?;
+    write!(f, "]")
+}
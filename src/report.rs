@@ -0,0 +1,245 @@
+//! HTML Reports
+//!
+//! Renders a [`MatchingSet`] as a self-contained HTML page to share after the matching, with a
+//! summary, every pair and the rank each side received, how often each rank was received, the
+//! blocking pairs, and the unmatched participants. The page has its styles inlined and loads no
+//! external assets.
+
+use crate::{
+    render::{rank, Rank, UnitLabel},
+    Big, Index, Kind, Little, MatchingSet, Names, PreferenceTable,
+};
+use alloc::{string::String, vec::Vec};
+use core::fmt;
+
+/// Inline Style Sheet
+const STYLE: &str = "body { font-family: sans-serif; margin: 2em auto; max-width: 60em; }
+table { border-collapse: collapse; margin-bottom: 1em; }
+th, td { border: 1px solid #ccc; padding: 0.3em 0.8em; text-align: left; }
+th { background: #f0f0f0; }
+td.rank { text-align: right; }";
+
+/// HTML Report Display
+#[derive(derivative::Derivative)]
+#[derivative(Debug(bound = "T: fmt::Debug"))]
+pub struct HtmlReport<'s, T = String> {
+    /// Preference Table
+    table: &'s PreferenceTable,
+
+    /// Matching Set
+    matching_set: &'s MatchingSet,
+
+    /// Names
+    names: &'s Names<T>,
+}
+
+impl<'s, T> HtmlReport<'s, T> {
+    /// Writes a list of the participants at `indices`, or a note that there are none.
+    #[inline]
+    fn write_list<'i, K, I>(&self, f: &mut fmt::Formatter, indices: I) -> fmt::Result
+    where
+        K: Kind,
+        I: IntoIterator<Item = &'i Index<K>>,
+        T: fmt::Display,
+    {
+        let mut indices = indices.into_iter().peekable();
+        if indices.peek().is_none() {
+            return writeln!(f, "<p>None.</p>");
+        }
+        writeln!(f, "<ul>")?;
+        for index in indices {
+            writeln!(f, "<li>{}</li>", Html(&self.names.label(*index)))?;
+        }
+        writeln!(f, "</ul>")
+    }
+}
+
+impl<'s, T> Clone for HtmlReport<'s, T> {
+    #[inline]
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<'s, T> Copy for HtmlReport<'s, T> {}
+
+impl<'s, T> fmt::Display for HtmlReport<'s, T>
+where
+    T: fmt::Display,
+{
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let matching_set = self.matching_set;
+        let pairs = matching_set.pairs().collect::<Vec<_>>();
+        let blocking_pairs = matching_set.blocking_pairs(self.table);
+        writeln!(f, "<!DOCTYPE html>\n<html lang=\"en\">\n<head>")?;
+        writeln!(
+            f,
+            "<meta charset=\"utf-8\">\n<title>Big-Little Matching</title>"
+        )?;
+        writeln!(f, "<style>\n{STYLE}\n</style>\n</head>\n<body>")?;
+        writeln!(f, "<h1>Big-Little Matching</h1>\n<h2>Summary</h2>\n<table>")?;
+        for (label, count) in [
+            ("Bigs", self.table.big_preferences.len()),
+            ("Littles", self.table.little_preferences.len()),
+            ("Pairs", pairs.len()),
+            ("Unmatched Bigs", matching_set.unmatched_bigs().len()),
+            ("Unmatched Littles", matching_set.unmatched_littles().len()),
+            ("Mutual Pairs", self.table.statistics().mutual_pairs().len()),
+            ("Blocking Pairs", blocking_pairs.len()),
+        ] {
+            writeln!(
+                f,
+                "<tr><th>{label}</th><td class=\"rank\">{count}</td></tr>"
+            )?;
+        }
+        writeln!(f, "</table>\n<h2>Matches</h2>\n<table>")?;
+        writeln!(
+            f,
+            "<tr><th>Big</th><th>Little</th><th>Big's Rank of Little</th>\
+             <th>Little's Rank of Big</th></tr>"
+        )?;
+        let mut big_ranks = RankCounts::default();
+        let mut little_ranks = RankCounts::default();
+        for (matching, little) in matching_set
+            .matches()
+            .iter()
+            .flat_map(|m| m.littles.iter().map(move |l| (m, l)))
+        {
+            let big_rank = rank(self.table, matching.big, *little);
+            let little_rank = rank(self.table, *little, matching.big);
+            big_ranks.record(big_rank);
+            little_ranks.record(little_rank);
+            writeln!(
+                f,
+                "<tr><td>{}</td><td>{}</td>\
+                 <td class=\"rank\">{}</td><td class=\"rank\">{}</td></tr>",
+                Html(&UnitLabel(self.names, matching, None)),
+                Html(&self.names.label(*little)),
+                Rank(big_rank),
+                Rank(little_rank),
+            )?;
+        }
+        writeln!(f, "</table>\n<h2>Ranks Received</h2>\n<table>")?;
+        writeln!(f, "<tr><th>Rank</th><th>Bigs</th><th>Littles</th></tr>")?;
+        for rank in 0..big_ranks.ranked.len().max(little_ranks.ranked.len()) {
+            writeln!(
+                f,
+                "<tr><td class=\"rank\">{}</td>\
+                 <td class=\"rank\">{}</td><td class=\"rank\">{}</td></tr>",
+                rank + 1,
+                big_ranks.ranked.get(rank).copied().unwrap_or_default(),
+                little_ranks.ranked.get(rank).copied().unwrap_or_default(),
+            )?;
+        }
+        if big_ranks.unranked + little_ranks.unranked > 0 {
+            writeln!(
+                f,
+                "<tr><td>Unranked</td><td class=\"rank\">{}</td><td class=\"rank\">{}</td></tr>",
+                big_ranks.unranked, little_ranks.unranked,
+            )?;
+        }
+        writeln!(f, "</table>\n<h2>Blocking Pairs</h2>")?;
+        if blocking_pairs.is_empty() {
+            writeln!(f, "<p>None.</p>")?;
+        } else {
+            writeln!(f, "<table>\n<tr><th>Big</th><th>Little</th></tr>")?;
+            for (big, little) in &blocking_pairs {
+                writeln!(
+                    f,
+                    "<tr><td>{}</td><td>{}</td></tr>",
+                    Html(&self.names.label(*big)),
+                    Html(&self.names.label(*little)),
+                )?;
+            }
+            writeln!(f, "</table>")?;
+        }
+        writeln!(f, "<h2>Unmatched Bigs</h2>")?;
+        self.write_list::<Big, _>(f, matching_set.unmatched_bigs())?;
+        writeln!(f, "<h2>Unmatched Littles</h2>")?;
+        self.write_list::<Little, _>(f, matching_set.unmatched_littles())?;
+        write!(f, "</body>\n</html>")
+    }
+}
+
+impl MatchingSet {
+    /// Returns a [`Display`](fmt::Display) implementation for `self` which writes a self-contained
+    /// HTML report of the matching, looking up ranks in `table` and substituting `names` for
+    /// indices. See the [`report`](crate::report) module for what the report contains.
+    #[inline]
+    pub fn html<'s, T>(
+        &'s self,
+        table: &'s PreferenceTable,
+        names: &'s Names<T>,
+    ) -> HtmlReport<'s, T>
+    where
+        T: fmt::Display,
+    {
+        HtmlReport {
+            table,
+            matching_set: self,
+            names,
+        }
+    }
+}
+
+/// Received Rank Counts
+#[derive(Default)]
+struct RankCounts {
+    /// Number of Pairs at each Rank
+    ranked: Vec<usize>,
+
+    /// Number of Pairs without a Rank
+    unranked: usize,
+}
+
+impl RankCounts {
+    /// Records a pair at the one-based `rank`, if there is one.
+    #[inline]
+    fn record(&mut self, rank: Option<usize>) {
+        match rank {
+            Some(rank) => {
+                if self.ranked.len() < rank {
+                    self.ranked.resize(rank, 0);
+                }
+                self.ranked[rank - 1] += 1;
+            }
+            _ => self.unranked += 1,
+        }
+    }
+}
+
+/// Escaped HTML Text
+///
+/// Writes a value as HTML text, escaping the characters which have a meaning in markup.
+struct Html<'t, T>(&'t T);
+
+impl<'t, T> fmt::Display for Html<'t, T>
+where
+    T: fmt::Display,
+{
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        /// Escaping Writer
+        struct Escape<'f, 'g>(&'f mut fmt::Formatter<'g>);
+
+        impl fmt::Write for Escape<'_, '_> {
+            #[inline]
+            fn write_str(&mut self, s: &str) -> fmt::Result {
+                for c in s.chars() {
+                    match c {
+                        '&' => self.0.write_str("&amp;")?,
+                        '<' => self.0.write_str("&lt;")?,
+                        '>' => self.0.write_str("&gt;")?,
+                        '"' => self.0.write_str("&quot;")?,
+                        '\'' => self.0.write_str("&#39;")?,
+                        _ => fmt::Write::write_char(self.0, c)?,
+                    }
+                }
+                Ok(())
+            }
+        }
+
+        fmt::write(&mut Escape(f), format_args!("{}", self.0))
+    }
+}
@@ -0,0 +1,65 @@
+//! Matching Stability
+//!
+//! The [even matching](PreferenceTable::find_even_matching) spreads the littles evenly across the
+//! bigs instead of giving everyone their favorite, so a matching may have blocking pairs: a big and
+//! a little who rank each other and would both rather be matched together than with who they got.
+
+use crate::{BigIndex, LittleIndex, MatchingSet, PreferenceTable};
+use alloc::vec::Vec;
+use indexmap::IndexMap;
+
+impl MatchingSet {
+    /// Returns every blocking pair of `self` under the preferences in `table`, ordered by big
+    /// index and then by each big's preferences.
+    ///
+    /// A big and a little form a blocking pair if they rank each other but are not matched with
+    /// each other, the little is unmatched or prefers the big to their own, and the big has room
+    /// for another little or prefers the little to one of their own. Locked littles are never part
+    /// of a blocking pair since their pair is already confirmed. With co-big units and little
+    /// groups, the blocking pairs are between whole units and groups, each given by the big or
+    /// little who stands for it as described in the [`group`](crate::group) module.
+    #[inline]
+    pub fn blocking_pairs(&self, table: &PreferenceTable) -> Vec<(BigIndex, LittleIndex)> {
+        if let Some(reduced) = table.reduced() {
+            return self.reduced(table).blocking_pairs(&reduced);
+        }
+        let matched_bigs = self
+            .pairs()
+            .map(|(big, little)| (little, big))
+            .collect::<IndexMap<_, _>>();
+        let mut blocking_pairs = Vec::new();
+        for (big, row) in table.big_preferences.iter().enumerate() {
+            let big = BigIndex::from(big);
+            let littles = self.matching(big).map(|m| &m.littles);
+            let load = table.load(self, big);
+            let worst = littles.and_then(|littles| {
+                littles
+                    .iter()
+                    .filter_map(|l| big.preference(*l, table))
+                    .max()
+            });
+            for (position, little) in row.iter().enumerate() {
+                if table.is_locked(*little) {
+                    continue;
+                }
+                let rank = match little.preference(big, table) {
+                    Some(rank) => rank,
+                    _ => continue,
+                };
+                let little_prefers = match matched_bigs.get(little) {
+                    Some(current) if *current == big => continue,
+                    Some(current) => little
+                        .preference(*current, table)
+                        .is_none_or(|current| rank < current),
+                    _ => true,
+                };
+                let big_prefers = table.has_room(big, load, table.weight(*little))
+                    || worst.is_some_and(|worst| position + 1 < worst.preference.get() as usize);
+                if little_prefers && big_prefers {
+                    blocking_pairs.push((big, *little));
+                }
+            }
+        }
+        blocking_pairs
+    }
+}
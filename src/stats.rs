@@ -0,0 +1,202 @@
+//! Preference Statistics
+//!
+//! Summarizes a [`PreferenceTable`] before matching: how often each participant was ranked and at
+//! which positions, which pairs rank each other, and who has no mutually-acceptable partner at
+//! all.
+
+use crate::{
+    Big, BigIndex, Index, Kind, Little, LittleIndex, PreferenceTable, Select, SelectBase,
+    SelectType,
+};
+use alloc::{vec, vec::Vec};
+
+/// Rank Counts
+///
+/// Counts how many times one participant was ranked by the participants of the opposite kind.
+#[derive(Clone, Debug, Default, Eq, Hash, PartialEq)]
+pub struct RankCounts {
+    /// Number of Times Ranked at each Position
+    positions: Vec<usize>,
+
+    /// Number of Mutually-Acceptable Partners
+    mutual: usize,
+}
+
+impl RankCounts {
+    /// Records that the participant was ranked at the zero-based `position`.
+    #[inline]
+    fn record(&mut self, position: usize) {
+        if self.positions.len() <= position {
+            self.positions.resize(position + 1, 0);
+        }
+        self.positions[position] += 1;
+    }
+
+    /// Returns the number of times the participant was ranked at each position, starting with
+    /// the first choice.
+    #[inline]
+    pub fn positions(&self) -> &[usize] {
+        &self.positions
+    }
+
+    /// Returns the number of times the participant was ranked at the one-based `rank`.
+    #[inline]
+    pub fn at(&self, rank: usize) -> usize {
+        rank.checked_sub(1)
+            .and_then(|i| self.positions.get(i))
+            .copied()
+            .unwrap_or_default()
+    }
+
+    /// Returns the number of times the participant was ranked at all.
+    #[inline]
+    pub fn total(&self) -> usize {
+        self.positions.iter().sum()
+    }
+
+    /// Returns the number of participants of the opposite kind that the participant is mutually
+    /// acceptable with.
+    #[inline]
+    pub fn mutual(&self) -> usize {
+        self.mutual
+    }
+}
+
+/// Preference Statistics
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Statistics {
+    /// Big Rank Counts
+    bigs: Vec<RankCounts>,
+
+    /// Little Rank Counts
+    littles: Vec<RankCounts>,
+
+    /// Mutual Pairs
+    mutual_pairs: Vec<(BigIndex, LittleIndex)>,
+}
+
+impl Statistics {
+    /// Computes the statistics of the preferences in `table`.
+    #[inline]
+    pub fn new(table: &PreferenceTable) -> Self {
+        let mut bigs = vec![RankCounts::default(); table.big_preferences.len()];
+        let mut littles = vec![RankCounts::default(); table.little_preferences.len()];
+        for row in &table.big_preferences {
+            for (position, little) in row.iter().enumerate() {
+                if let Some(counts) = littles.get_mut(little.index as usize) {
+                    counts.record(position);
+                }
+            }
+        }
+        for row in &table.little_preferences {
+            for (position, big) in row.iter().enumerate() {
+                if let Some(counts) = bigs.get_mut(big.index as usize) {
+                    counts.record(position);
+                }
+            }
+        }
+        let mut mutual_pairs = Vec::new();
+        for (big, row) in table.big_preferences.iter().enumerate() {
+            let big = BigIndex::from(big);
+            for little in row {
+                let ranks_back = table
+                    .little_preferences
+                    .get(little.index as usize)
+                    .is_some_and(|row| row.contains(&big));
+                if ranks_back {
+                    mutual_pairs.push((big, *little));
+                    bigs[big.index as usize].mutual += 1;
+                    littles[little.index as usize].mutual += 1;
+                }
+            }
+        }
+        Self {
+            bigs,
+            littles,
+            mutual_pairs,
+        }
+    }
+
+    /// Returns the rank counts of the participant at `index`.
+    #[inline]
+    pub fn rank_counts<K>(&self, index: Index<K>) -> Option<&RankCounts>
+    where
+        K: Kind,
+    {
+        K::select(self).get(index.index as usize)
+    }
+
+    /// Returns an iterator over the rank counts of every participant of kind `K`.
+    #[inline]
+    pub fn iter<K>(&self) -> impl Iterator<Item = (Index<K>, &RankCounts)>
+    where
+        K: Kind,
+    {
+        K::select(self)
+            .iter()
+            .enumerate()
+            .map(|(i, counts)| (Index::from(i), counts))
+    }
+
+    /// Returns the largest number of positions at which any participant of kind `K` was ranked.
+    #[inline]
+    pub fn longest_ranking<K>(&self) -> usize
+    where
+        K: Kind,
+    {
+        K::select(self)
+            .iter()
+            .map(|c| c.positions.len())
+            .max()
+            .unwrap_or_default()
+    }
+
+    /// Returns every pair of a big and a little who rank each other, ordered by big index and then
+    /// by each big's preferences.
+    #[inline]
+    pub fn mutual_pairs(&self) -> &[(BigIndex, LittleIndex)] {
+        &self.mutual_pairs
+    }
+
+    /// Returns an iterator over the participants of kind `K` who have no mutually-acceptable
+    /// partner and so can never be matched.
+    #[inline]
+    pub fn without_mutual<K>(&self) -> impl Iterator<Item = Index<K>> + '_
+    where
+        K: Kind,
+    {
+        self.iter::<K>()
+            .filter(|(_, counts)| counts.mutual == 0)
+            .map(|(index, _)| index)
+    }
+}
+
+impl<K> SelectBase<K> for Statistics
+where
+    K: Kind,
+{
+    type Type = Vec<RankCounts>;
+}
+
+impl<K> Select<K> for Statistics
+where
+    K: Kind,
+{
+    #[inline]
+    fn big(&self) -> &SelectType<Big, Self> {
+        &self.bigs
+    }
+
+    #[inline]
+    fn little(&self) -> &SelectType<Little, Self> {
+        &self.littles
+    }
+}
+
+impl PreferenceTable {
+    /// Computes the [`Statistics`] of the preferences in `self`.
+    #[inline]
+    pub fn statistics(&self) -> Statistics {
+        Statistics::new(self)
+    }
+}
@@ -0,0 +1,306 @@
+//! Matching Traces
+//!
+//! The solvers in [`PreferenceTable`](crate::PreferenceTable) can report every decision they make
+//! to an [`Observer`]. The [`Trace`] observer records these [`Event`]s so that the outcome for any
+//! participant can be explained after the fact.
+
+use crate::{BigIndex, DynamicKind, Index, Kind, LittleIndex, Names};
+use alloc::{string::String, vec::Vec};
+use core::fmt;
+
+/// Matching Event
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Event {
+    /// Locked
+    ///
+    /// The `little` is locked to the `big` and matched with them before anyone else.
+    Locked {
+        /// Little Index
+        little: LittleIndex,
+
+        /// Big Index
+        big: BigIndex,
+    },
+
+    /// Proposal
+    ///
+    /// The `little` proposes to the next `big` in their preferences.
+    Proposal {
+        /// Little Index
+        little: LittleIndex,
+
+        /// Big Index
+        big: BigIndex,
+    },
+
+    /// Acceptance
+    ///
+    /// The `big` ranks the `little` and tentatively accepts their proposal.
+    Acceptance {
+        /// Little Index
+        little: LittleIndex,
+
+        /// Big Index
+        big: BigIndex,
+    },
+
+    /// Rejection
+    ///
+    /// The `big` does not rank the `little` so the `little` moves down their preferences.
+    Rejection {
+        /// Little Index
+        little: LittleIndex,
+
+        /// Big Index
+        big: BigIndex,
+    },
+
+    /// Full
+    ///
+    /// The `big` ranks the `little` but is already at capacity so the `little` moves down their
+    /// preferences.
+    Full {
+        /// Little Index
+        little: LittleIndex,
+
+        /// Big Index
+        big: BigIndex,
+    },
+
+    /// Exhaustion
+    ///
+    /// The `little` has proposed to every big in their preferences without being accepted.
+    Exhaustion {
+        /// Little Index
+        little: LittleIndex,
+    },
+
+    /// Shift
+    ///
+    /// The `little` is moved out of the matching with the `from` big and into the matching with
+    /// the `to` big to even out the number of littles of each big.
+    Shift {
+        /// Little Index
+        little: LittleIndex,
+
+        /// Source Big Index
+        from: BigIndex,
+
+        /// Target Big Index
+        to: BigIndex,
+    },
+
+    /// Matched
+    ///
+    /// The `little` is matched with the `big` in the final matching.
+    Matched {
+        /// Little Index
+        little: LittleIndex,
+
+        /// Big Index
+        big: BigIndex,
+    },
+
+    /// Unmatched Little
+    ///
+    /// The `little` is left unmatched in the final matching.
+    UnmatchedLittle {
+        /// Little Index
+        little: LittleIndex,
+    },
+
+    /// Unmatched Big
+    ///
+    /// The `big` is left unmatched in the final matching.
+    UnmatchedBig {
+        /// Big Index
+        big: BigIndex,
+    },
+}
+
+impl Event {
+    /// Returns the little involved in `self` if there is one.
+    #[inline]
+    pub fn little(&self) -> Option<LittleIndex> {
+        match self {
+            Self::Locked { little, .. }
+            | Self::Proposal { little, .. }
+            | Self::Acceptance { little, .. }
+            | Self::Rejection { little, .. }
+            | Self::Full { little, .. }
+            | Self::Exhaustion { little }
+            | Self::Shift { little, .. }
+            | Self::Matched { little, .. }
+            | Self::UnmatchedLittle { little } => Some(*little),
+            Self::UnmatchedBig { .. } => None,
+        }
+    }
+
+    /// Returns `true` if `big` is involved in `self`.
+    #[inline]
+    pub fn contains_big(&self, big: BigIndex) -> bool {
+        match self {
+            Self::Locked { big: b, .. }
+            | Self::Proposal { big: b, .. }
+            | Self::Acceptance { big: b, .. }
+            | Self::Rejection { big: b, .. }
+            | Self::Full { big: b, .. }
+            | Self::Matched { big: b, .. }
+            | Self::UnmatchedBig { big: b } => *b == big,
+            Self::Shift { from, to, .. } => *from == big || *to == big,
+            Self::Exhaustion { .. } | Self::UnmatchedLittle { .. } => false,
+        }
+    }
+
+    /// Returns `true` if the participant at `index` with kind `K` is involved in `self`.
+    #[inline]
+    pub fn contains<K>(&self, index: Index<K>) -> bool
+    where
+        K: Kind,
+    {
+        match K::dynamic() {
+            DynamicKind::Big => self.contains_big(Index::new(index.index)),
+            DynamicKind::Little => self.little() == Some(Index::new(index.index)),
+        }
+    }
+
+    /// Returns a [`Display`](fmt::Display) implementation for `self` which substitutes `names` for
+    /// indices in the event.
+    #[inline]
+    pub fn display<'s, T>(&'s self, names: &'s Names<T>) -> EventDisplay<'s, T>
+    where
+        T: fmt::Display,
+    {
+        EventDisplay { event: self, names }
+    }
+}
+
+/// Event Display
+#[derive(derivative::Derivative)]
+#[derivative(Debug(bound = "T: fmt::Debug"))]
+pub struct EventDisplay<'s, T = String> {
+    /// Event
+    event: &'s Event,
+
+    /// Names
+    names: &'s Names<T>,
+}
+
+impl<'s, T> Clone for EventDisplay<'s, T> {
+    #[inline]
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<'s, T> Copy for EventDisplay<'s, T> {}
+
+impl<'s, T> fmt::Display for EventDisplay<'s, T>
+where
+    T: fmt::Display,
+{
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let names = self.names;
+        match *self.event {
+            Event::Locked { little, big } => write!(
+                f,
+                "{} is locked to {}.",
+                names.label(little),
+                names.label(big)
+            ),
+            Event::Proposal { little, big } => write!(
+                f,
+                "{} proposes to {}.",
+                names.label(little),
+                names.label(big)
+            ),
+            Event::Acceptance { little, big } => write!(
+                f,
+                "{} ranks {} and tentatively accepts.",
+                names.label(big),
+                names.label(little)
+            ),
+            Event::Rejection { little, big } => write!(
+                f,
+                "{} does not rank {}.",
+                names.label(big),
+                names.label(little)
+            ),
+            Event::Full { little, big } => write!(
+                f,
+                "{} ranks {} but is already full.",
+                names.label(big),
+                names.label(little)
+            ),
+            Event::Exhaustion { little } => {
+                write!(f, "{} has no bigs left to propose to.", names.label(little))
+            }
+            Event::Shift { little, from, to } => write!(
+                f,
+                "{} moves from {} to {} to even out the matching.",
+                names.label(little),
+                names.label(from),
+                names.label(to)
+            ),
+            Event::Matched { little, big } => write!(
+                f,
+                "{} is matched with {}.",
+                names.label(little),
+                names.label(big)
+            ),
+            Event::UnmatchedLittle { little } => {
+                write!(f, "{} is left unmatched.", names.label(little))
+            }
+            Event::UnmatchedBig { big } => {
+                write!(f, "{} is left unmatched.", names.label(big))
+            }
+        }
+    }
+}
+
+/// Matching Observer
+pub trait Observer {
+    /// Observes the next `event` reported by a solver.
+    fn observe(&mut self, event: Event);
+}
+
+impl Observer for () {
+    #[inline]
+    fn observe(&mut self, event: Event) {
+        let _ = event;
+    }
+}
+
+/// Matching Trace
+///
+/// Records every [`Event`] reported by a solver in the order they happened.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Trace {
+    /// Events
+    events: Vec<Event>,
+}
+
+impl Trace {
+    /// Returns all the events recorded in `self`.
+    #[inline]
+    pub fn events(&self) -> &[Event] {
+        &self.events
+    }
+
+    /// Returns an iterator over the events in `self` which involve the participant at `index`.
+    #[inline]
+    pub fn events_for<K>(&self, index: Index<K>) -> impl Iterator<Item = &Event>
+    where
+        K: Kind,
+    {
+        self.events.iter().filter(move |e| e.contains(index))
+    }
+}
+
+impl Observer for Trace {
+    #[inline]
+    fn observe(&mut self, event: Event) {
+        self.events.push(event);
+    }
+}
@@ -0,0 +1,22 @@
+//! Shared Test Fixtures
+
+#![allow(dead_code)]
+
+use biglittle::{Big, BigIndex, Little, LittleIndex, PreferenceTable};
+
+/// Builds a [`PreferenceTable`] from the raw `bigs` and `littles` preference rows.
+pub fn table(bigs: &[&[u32]], littles: &[&[u32]]) -> PreferenceTable {
+    let mut table = PreferenceTable::default();
+    for row in bigs {
+        table.insert::<Big, _>(row.iter().map(|i| LittleIndex::new(*i)));
+    }
+    for row in littles {
+        table.insert::<Little, _>(row.iter().map(|i| BigIndex::new(*i)));
+    }
+    table
+}
+
+/// Builds a big-little pair from raw indices.
+pub fn pair(big: u32, little: u32) -> (BigIndex, LittleIndex) {
+    (BigIndex::new(big), LittleIndex::new(little))
+}
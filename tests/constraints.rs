@@ -0,0 +1,100 @@
+//! Matching Constraint Tests
+
+mod common;
+
+use biglittle::{
+    trace::{Event, Trace},
+    BigIndex, LittleIndex, PreferenceTable,
+};
+use common::table;
+
+/// Returns the big matched with `little` in the even matching of `table`.
+fn big_of(table: &PreferenceTable, little: u32) -> Option<BigIndex> {
+    table
+        .find_even_matching()
+        .pairs()
+        .find(|(_, l)| *l == LittleIndex::new(little))
+        .map(|(big, _)| big)
+}
+
+/// Checks that a full big turns littles away in the maximal matching.
+#[test]
+fn full_big_rejects_littles() {
+    let mut table = table(&[&[0, 1], &[1]], &[&[0], &[0, 1]]);
+    table.set_capacity(BigIndex::new(0), 1);
+    let mut trace = Trace::default();
+    let matching_set = table.find_maximal_matching_with_observer(&mut trace);
+    assert!(trace.events().contains(&Event::Full {
+        little: LittleIndex::new(1),
+        big: BigIndex::new(0),
+    }));
+    assert_eq!(
+        matching_set.pairs().collect::<Vec<_>>(),
+        [
+            (BigIndex::new(0), LittleIndex::new(0)),
+            (BigIndex::new(1), LittleIndex::new(1))
+        ]
+    );
+}
+
+/// Checks that a little left out by a full big is matched by moving another little away.
+#[test]
+fn augmenting_path_makes_room() {
+    let mut table = table(&[&[0, 1], &[0]], &[&[0, 1], &[0]]);
+    table.set_capacity(BigIndex::new(0), 1);
+    let maximal = table.find_maximal_matching();
+    assert!(maximal.unmatched_littles().contains(&LittleIndex::new(1)));
+    let even = table.find_even_matching();
+    assert!(even.unmatched_littles().is_empty());
+    assert_eq!(big_of(&table, 0), Some(BigIndex::new(1)));
+    assert_eq!(big_of(&table, 1), Some(BigIndex::new(0)));
+}
+
+/// Checks that forbidden pairs are never matched.
+#[test]
+fn forbidden_pair_is_not_matched() {
+    let mut table = table(&[&[0], &[0]], &[&[0, 1]]);
+    assert_eq!(big_of(&table, 0), Some(BigIndex::new(0)));
+    table.forbid(BigIndex::new(0), LittleIndex::new(0));
+    assert_eq!(big_of(&table, 0), Some(BigIndex::new(1)));
+    table.forbid(BigIndex::new(1), LittleIndex::new(0));
+    assert_eq!(big_of(&table, 0), None);
+}
+
+/// Checks that forced pairs are matched even if the big did not rank the little.
+#[test]
+fn forced_pair_is_matched() {
+    let mut table = table(&[&[0], &[]], &[&[0, 1]]);
+    table.force(BigIndex::new(1), LittleIndex::new(0));
+    assert_eq!(big_of(&table, 0), Some(BigIndex::new(1)));
+    assert_eq!(
+        table
+            .find_even_matching()
+            .unmatched_bigs()
+            .iter()
+            .copied()
+            .collect::<Vec<_>>(),
+        [BigIndex::new(0)]
+    );
+}
+
+/// Checks that locked pairs are kept and count against the capacity of their big.
+#[test]
+fn locked_pair_is_kept() {
+    let mut table = table(&[&[0, 1], &[0]], &[&[0, 1], &[1]]);
+    table.set_capacity(BigIndex::new(0), 1);
+    table.lock(BigIndex::new(0), LittleIndex::new(1));
+    let mut trace = Trace::default();
+    table.find_even_matching_with_observer(&mut trace);
+    assert_eq!(
+        trace.events()[0],
+        Event::Locked {
+            little: LittleIndex::new(1),
+            big: BigIndex::new(0),
+        }
+    );
+    assert_eq!(big_of(&table, 1), Some(BigIndex::new(0)));
+    assert_eq!(big_of(&table, 0), Some(BigIndex::new(1)));
+    assert_eq!(table.unlock(LittleIndex::new(1)), Some(BigIndex::new(0)));
+    assert_eq!(big_of(&table, 0), Some(BigIndex::new(0)));
+}
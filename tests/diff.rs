@@ -0,0 +1,63 @@
+//! Matching Difference Tests
+
+use biglittle::{diff::Move, Big, BigIndex, Little, LittleIndex, MatchingSet};
+
+/// Builds a [`MatchingSet`] from raw `pairs` and unmatched participants.
+fn matching_set(pairs: &[(u32, u32)], bigs: &[u32], littles: &[u32]) -> MatchingSet {
+    MatchingSet::from_parts(
+        pairs
+            .iter()
+            .map(|(big, little)| (BigIndex::new(*big), LittleIndex::new(*little))),
+        bigs.iter().map(|i| BigIndex::new(*i)),
+        littles.iter().map(|i| LittleIndex::new(*i)),
+    )
+}
+
+/// Checks that added, removed, and moved littles and unmatched changes are all reported.
+#[test]
+fn pair_changes() {
+    let old = matching_set(&[(0, 0), (0, 1), (1, 2)], &[2], &[3]);
+    let new = matching_set(&[(0, 0), (1, 3), (2, 2)], &[], &[1]);
+    let diff = old.diff(&new);
+    assert_eq!(diff.added(), [(BigIndex::new(1), LittleIndex::new(3))]);
+    assert_eq!(diff.removed(), [(BigIndex::new(0), LittleIndex::new(1))]);
+    assert_eq!(
+        diff.moved(),
+        [Move {
+            little: LittleIndex::new(2),
+            from: BigIndex::new(1),
+            to: BigIndex::new(2),
+        }]
+    );
+    assert!(diff.reordered().is_empty());
+    assert!(diff.unmatched::<Big>().newly_unmatched().is_empty());
+    assert_eq!(
+        diff.unmatched::<Big>().no_longer_unmatched(),
+        [BigIndex::new(2)]
+    );
+    assert_eq!(
+        diff.unmatched::<Little>().newly_unmatched(),
+        [LittleIndex::new(1)]
+    );
+    assert_eq!(
+        diff.unmatched::<Little>().no_longer_unmatched(),
+        [LittleIndex::new(3)]
+    );
+    assert!(old.diff(&old).is_empty());
+}
+
+/// Checks that changes in the order of the littles of a big can be ignored.
+#[test]
+fn order_changes() {
+    let old = matching_set(&[(0, 0), (0, 1), (0, 2)], &[], &[]);
+    let new = matching_set(&[(0, 1), (0, 0)], &[], &[2]);
+    let diff = old.diff(&new);
+    assert_eq!(diff.reordered(), [BigIndex::new(0)]);
+    assert_eq!(diff.removed(), [(BigIndex::new(0), LittleIndex::new(2))]);
+    let diff = old.diff_ignoring_order(&new);
+    assert!(diff.reordered().is_empty());
+    assert!(!diff.is_empty());
+    assert!(old
+        .diff_ignoring_order(&matching_set(&[(0, 2), (0, 1), (0, 0)], &[], &[]))
+        .is_empty());
+}
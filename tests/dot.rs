@@ -0,0 +1,36 @@
+//! Graphviz DOT Rendering Tests
+
+use biglittle::{Big, BigIndex, Little, LittleIndex, MatchingSet, Names, PreferenceTable};
+
+/// Checks that the nodes, ranks, matched pairs, and unmatched participants are all rendered.
+#[test]
+fn preference_graph() {
+    let mut names = Names::default();
+    names.insert::<Big>("Alice");
+    names.insert::<Big>("Bob");
+    names.insert::<Little>("Dan \"D\"");
+    names.insert::<Little>("Erin");
+    let mut table = PreferenceTable::default();
+    table.insert::<Big, _>([LittleIndex::new(0), LittleIndex::new(1)]);
+    table.insert::<Big, _>([]);
+    table.insert::<Little, _>([BigIndex::new(0)]);
+    table.insert::<Little, _>([BigIndex::new(1), BigIndex::new(0)]);
+    let matching_set = MatchingSet::from_parts(
+        [(BigIndex::new(0), LittleIndex::new(0))],
+        [BigIndex::new(1)],
+        [LittleIndex::new(1)],
+    );
+    assert_eq!(
+        table.dot(&names).matching(&matching_set).to_string(),
+        "graph preferences {\n    rankdir = LR;\n    subgraph bigs {\n        \
+         rank = same;\n        node [shape = box];\n        big0 [label = \"Alice\"];\n        \
+         big1 [label = \"Bob\", style = \"dashed,filled\", fillcolor = lightgray];\n    }\n    \
+         subgraph littles {\n        rank = same;\n        node [shape = ellipse];\n        \
+         little0 [label = \"Dan \\\"D\\\"\"];\n        \
+         little1 [label = \"Erin\", style = \"dashed,filled\", fillcolor = lightgray];\n    }\n    \
+         big0 -- little0 [label = \"1/1\", color = \"#1a9850\", penwidth = 3];\n    \
+         big0 -- little1 [label = \"2/2\", color = \"#91cf60\"];\n    \
+         big1 -- little1 [label = \"-/1\", color = \"#1a9850\", style = dashed];\n}"
+    );
+    assert!(!table.dot(&names).to_string().contains("penwidth"));
+}
@@ -0,0 +1,64 @@
+//! Dynamic Kind API Tests
+
+use biglittle::{
+    dynamic::{DynIndex, KindMismatch},
+    Big, BigIndex, DynamicKind, Index, Little, LittleIndex, Names, PreferenceTable,
+};
+
+/// Checks that typed indices round-trip through [`DynIndex`] and that the wrong kind is rejected.
+#[test]
+fn typed_index_round_trip() {
+    let big = BigIndex::new(3);
+    let index = DynIndex::from(big);
+    assert_eq!(index, DynIndex::new(DynamicKind::Big, 3));
+    assert_eq!(BigIndex::try_from(index), Ok(big));
+    assert_eq!(
+        LittleIndex::try_from(index),
+        Err(KindMismatch {
+            expected: DynamicKind::Little,
+            found: DynamicKind::Big,
+        })
+    );
+}
+
+/// Checks that names inserted dynamically can be found with both the typed and dynamic APIs.
+#[test]
+fn names_insert_dyn() {
+    let mut names = Names::default();
+    let alice = names.insert_dyn(DynamicKind::Big, "Alice".into()).unwrap();
+    let bob = names.insert_dyn(DynamicKind::Little, "Bob".into()).unwrap();
+    assert_eq!(names.insert_dyn(DynamicKind::Little, "Alice".into()), None);
+    assert_eq!(names.index::<Big>("Alice"), Some(Index::new(0)));
+    assert_eq!(names.index_dyn(DynamicKind::Little, "Bob"), Some(bob));
+    assert_eq!(names.find("Alice"), Some(alice));
+    assert_eq!(names.find("Bob"), Some(bob));
+    assert_eq!(names.find("Carol"), None);
+    assert_eq!(names.get_dyn(bob).map(String::as_str), Some("Bob"));
+}
+
+/// Checks that dynamically inserted preference rows behave like typed ones and that rows with the
+/// wrong kind of preferences are rejected without being inserted.
+#[test]
+fn preference_table_insert_dyn() {
+    let big = DynIndex::new(DynamicKind::Big, 0);
+    let little = DynIndex::new(DynamicKind::Little, 0);
+    let mut table = PreferenceTable::default();
+    assert_eq!(
+        table.insert_dyn(DynamicKind::Big, [little, big]),
+        Err(KindMismatch {
+            expected: DynamicKind::Little,
+            found: DynamicKind::Big,
+        })
+    );
+    assert_eq!(table.get_dyn(big), None);
+    table.insert_dyn(DynamicKind::Big, [little]).unwrap();
+    table.insert_dyn(DynamicKind::Little, [big]).unwrap();
+    let mut typed = PreferenceTable::default();
+    typed.insert::<Big, _>([LittleIndex::new(0)]);
+    typed.insert::<Little, _>([BigIndex::new(0)]);
+    assert_eq!(table, typed);
+    assert_eq!(table.get_dyn(big), Some(vec![little]));
+    let matching_set = table.find_even_matching();
+    assert_eq!(matching_set.partners_dyn(big), vec![little]);
+    assert_eq!(matching_set.partners_dyn(little), vec![big]);
+}
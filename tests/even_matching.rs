@@ -0,0 +1,123 @@
+//! Even Matching Regression Tests
+
+mod common;
+
+use biglittle::{BigIndex, LittleIndex, PreferenceTable};
+use common::table;
+
+/// Returns the littles matched with `big` in the even matching, in the big's preference order.
+fn littles(table: &PreferenceTable, big: u32) -> Vec<LittleIndex> {
+    table
+        .find_even_matching()
+        .matching(BigIndex::new(big))
+        .map(|m| m.littles.iter().copied().collect())
+        .unwrap_or_default()
+}
+
+/// Checks that a single big keeps every little that ranks it.
+#[test]
+fn single_big_keeps_all_littles() {
+    let table = table(&[&[0, 1, 2]], &[&[0], &[0], &[0]]);
+    let matching_set = table.find_even_matching();
+    assert_eq!(matching_set, table.find_maximal_matching());
+    assert_eq!(
+        littles(&table, 0),
+        vec![
+            LittleIndex::new(0),
+            LittleIndex::new(1),
+            LittleIndex::new(2)
+        ]
+    );
+    assert!(matching_set.unmatched_bigs().is_empty());
+    assert!(matching_set.unmatched_littles().is_empty());
+}
+
+/// Checks that a big nobody ranks does not cause the other bigs to lose their littles.
+#[test]
+fn unranked_big_does_not_drain_other_bigs() {
+    let table = table(&[&[0, 1], &[0, 1]], &[&[0], &[0]]);
+    let matching_set = table.find_even_matching();
+    assert_eq!(
+        littles(&table, 0),
+        vec![LittleIndex::new(0), LittleIndex::new(1)]
+    );
+    assert_eq!(matching_set.matches().len(), 1);
+    assert!(matching_set.unmatched_bigs().contains(&BigIndex::new(1)));
+    assert!(matching_set.unmatched_littles().is_empty());
+}
+
+/// Checks that a big which ranks nobody stays unmatched while the other bigs are balanced.
+#[test]
+fn big_ranking_nobody_is_unmatched() {
+    let table = table(&[&[0, 1], &[], &[1, 0]], &[&[0, 1, 2], &[0, 1, 2]]);
+    let matching_set = table.find_even_matching();
+    assert_eq!(littles(&table, 0), vec![LittleIndex::new(0)]);
+    assert_eq!(littles(&table, 2), vec![LittleIndex::new(1)]);
+    assert!(matching_set.unmatched_bigs().contains(&BigIndex::new(1)));
+}
+
+/// Checks that littles who rank nobody are unmatched and never create empty matchings.
+#[test]
+fn littles_ranking_nobody_are_unmatched() {
+    let table = table(&[&[0, 1], &[1]], &[&[], &[]]);
+    let matching_set = table.find_even_matching();
+    assert!(matching_set.matches().is_empty());
+    assert_eq!(matching_set.unmatched_bigs().len(), 2);
+    assert_eq!(matching_set.unmatched_littles().len(), 2);
+}
+
+/// Checks that the little the fullest big ranks lowest is the one that moves.
+#[test]
+fn fullest_big_gives_up_lowest_ranked_little() {
+    let table = table(&[&[0, 1, 2], &[2, 1]], &[&[0], &[0, 1], &[0, 1]]);
+    assert_eq!(
+        littles(&table, 0),
+        vec![LittleIndex::new(0), LittleIndex::new(1)]
+    );
+    assert_eq!(littles(&table, 1), vec![LittleIndex::new(2)]);
+}
+
+/// Checks that load is passed along a chain of bigs when the fullest big cannot reach the
+/// emptiest big directly.
+#[test]
+fn load_moves_along_chain() {
+    let table = table(
+        &[&[0, 1, 2], &[3, 4, 2], &[4]],
+        &[&[0], &[0], &[0, 1], &[1], &[1, 2]],
+    );
+    let matching_set = table.find_even_matching();
+    assert_eq!(
+        littles(&table, 0),
+        vec![LittleIndex::new(0), LittleIndex::new(1)]
+    );
+    assert_eq!(
+        littles(&table, 1),
+        vec![LittleIndex::new(3), LittleIndex::new(2)]
+    );
+    assert_eq!(littles(&table, 2), vec![LittleIndex::new(4)]);
+    assert!(matching_set.unmatched_bigs().is_empty());
+}
+
+/// Checks that an already balanced maximal matching is left untouched.
+#[test]
+fn balanced_matching_is_unchanged() {
+    let table = table(&[&[0, 1], &[1, 0]], &[&[0, 1], &[1, 0]]);
+    assert_eq!(table.find_even_matching(), table.find_maximal_matching());
+}
+
+/// Checks that the step-by-step solver starts at the maximal matching and stops after the last
+/// shift.
+#[test]
+fn steps_start_at_maximal_matching() {
+    let table = table(
+        &[&[0, 1, 2], &[3, 4, 2], &[4]],
+        &[&[0], &[0], &[0, 1], &[1], &[1, 2]],
+    );
+    let mut solver = table.even_matching();
+    assert_eq!(solver.step(), Some(&table.find_maximal_matching()));
+    assert_eq!(solver.step(), Some(&table.find_even_matching()));
+    assert_eq!(solver.step(), None);
+    assert!(solver.is_finished());
+    assert_eq!(solver.steps(), 2);
+    assert_eq!(solver.finish(), table.find_even_matching());
+}
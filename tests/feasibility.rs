@@ -0,0 +1,57 @@
+//! Feasibility Analysis Tests
+
+mod common;
+
+use biglittle::{BigIndex, LittleIndex};
+use common::table;
+
+/// Checks that a table where everyone can be matched is reported as feasible.
+#[test]
+fn feasible_table() {
+    let mut table = table(&[&[0, 1], &[1, 2]], &[&[0], &[0, 1], &[1]]);
+    table.set_capacity(BigIndex::new(0), 1);
+    table.set_capacity(BigIndex::new(1), 2);
+    let feasibility = table.feasibility();
+    assert!(feasibility.is_feasible());
+    assert_eq!(feasibility.maximum_matched(), 3);
+    assert!(feasibility.unmatchable().is_empty());
+    assert!(feasibility.violations().is_empty());
+}
+
+/// Checks that littles without any mutually-acceptable big with room are unmatchable.
+#[test]
+fn unmatchable_littles() {
+    let mut table = table(&[&[0], &[1]], &[&[0], &[1], &[0, 1]]);
+    table.set_capacity(BigIndex::new(1), 0);
+    let feasibility = table.feasibility();
+    assert!(!feasibility.is_feasible());
+    assert_eq!(
+        feasibility.unmatchable(),
+        [LittleIndex::new(1), LittleIndex::new(2)]
+    );
+    assert!(feasibility.violations().is_empty());
+}
+
+/// Checks that a group of littles who only list bigs without enough room is reported.
+#[test]
+fn hall_violation() {
+    let mut table = table(
+        &[&[0, 1, 2], &[2, 3], &[4]],
+        &[&[0], &[0], &[0, 1], &[1], &[2]],
+    );
+    table.set_capacity(BigIndex::new(0), 1);
+    table.set_capacity(BigIndex::new(1), 1);
+    let feasibility = table.feasibility();
+    assert_eq!(feasibility.maximum_matched(), 3);
+    assert_eq!(feasibility.violations().len(), 1);
+    let violation = &feasibility.violations()[0];
+    assert_eq!(
+        violation.littles().iter().copied().collect::<Vec<_>>(),
+        (0..4).map(LittleIndex::new).collect::<Vec<_>>()
+    );
+    assert_eq!(
+        violation.bigs().iter().copied().collect::<Vec<_>>(),
+        [BigIndex::new(0), BigIndex::new(1)]
+    );
+    assert_eq!((violation.capacity(), violation.deficit()), (2, 2));
+}
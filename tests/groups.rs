@@ -0,0 +1,147 @@
+//! Co-Big Unit and Little Group Tests
+
+mod common;
+
+use biglittle::{Big, BigIndex, Little, LittleIndex, Names};
+use common::{pair, table};
+
+/// Checks that paired bigs are matched as one unit with their capacities combined, and that each
+/// of their littles is attributed to a member who ranks them back.
+#[test]
+fn co_big_units() {
+    let mut table = table(&[&[0], &[1], &[0, 1, 2]], &[&[0, 2], &[1, 2], &[2]]);
+    for big in 0..3 {
+        table.set_capacity(BigIndex::new(big), 1);
+    }
+    table.pair_co_bigs(BigIndex::new(1), BigIndex::new(0));
+    assert_eq!(
+        table.unit(BigIndex::new(1)),
+        [BigIndex::new(0), BigIndex::new(1)]
+    );
+    assert_eq!(table.unit(BigIndex::new(2)), [BigIndex::new(2)]);
+    let matching_set = table.find_even_matching();
+    assert_eq!(
+        matching_set.pairs().collect::<Vec<_>>(),
+        [pair(0, 0), pair(1, 1), pair(2, 2)]
+    );
+    let matching = matching_set.matching(BigIndex::new(1)).unwrap();
+    assert_eq!(
+        matching.co_bigs.iter().copied().collect::<Vec<_>>(),
+        [BigIndex::new(0)]
+    );
+    assert!(matching_set.unmatched_bigs().is_empty());
+    let mut names = Names::default();
+    for big in ["Alice", "Bob", "Carol"] {
+        names.insert::<Big>(big);
+    }
+    for little in ["Dan", "Erin", "Frank"] {
+        names.insert::<Little>(little);
+    }
+    let display = matching_set.display(&names).to_string();
+    assert!(display.contains("Alice & Bob: [Dan],"));
+    assert!(display.contains("Bob & Alice: [Erin],"));
+}
+
+/// Checks that grouped littles are matched with the same big or left unmatched together.
+#[test]
+fn little_groups() {
+    let mut table = table(&[&[0, 1, 2], &[0, 1, 2]], &[&[0, 1], &[1, 0], &[0, 1]]);
+    table.set_capacity(BigIndex::new(0), 2);
+    table.set_capacity(BigIndex::new(1), 2);
+    table.group_littles([LittleIndex::new(1), LittleIndex::new(0)]);
+    assert_eq!(
+        table.group(LittleIndex::new(0)),
+        [LittleIndex::new(0), LittleIndex::new(1)]
+    );
+    assert_eq!(
+        table.find_even_matching().pairs().collect::<Vec<_>>(),
+        [pair(0, 0), pair(0, 1), pair(1, 2)]
+    );
+    table.set_capacity(BigIndex::new(0), 1);
+    table.set_capacity(BigIndex::new(1), 1);
+    let matching_set = table.find_even_matching();
+    assert_eq!(matching_set.pairs().collect::<Vec<_>>(), [pair(0, 2)]);
+    assert_eq!(
+        matching_set
+            .unmatched_littles()
+            .iter()
+            .copied()
+            .collect::<Vec<_>>(),
+        [LittleIndex::new(0), LittleIndex::new(1)]
+    );
+    let feasibility = table.feasibility();
+    assert_eq!(feasibility.maximum_matched(), 1);
+    assert_eq!(
+        feasibility.unmatchable(),
+        [LittleIndex::new(0), LittleIndex::new(1)]
+    );
+}
+
+/// Checks that the even matching counts a group by its size when evening out the bigs.
+#[test]
+fn weighted_balance() {
+    let everyone: &[u32] = &[0, 1, 2, 3];
+    let both: &[u32] = &[0, 1];
+    let mut table = table(&[everyone, everyone], &[both; 4]);
+    table.group_littles([LittleIndex::new(0), LittleIndex::new(1)]);
+    assert_eq!(table.find_maximal_matching().pairs().count(), 4);
+    assert_eq!(
+        table.find_even_matching().pairs().collect::<Vec<_>>(),
+        [pair(0, 0), pair(0, 1), pair(1, 2), pair(1, 3)]
+    );
+}
+
+/// Checks that a group is moved as a whole when their big withdraws.
+#[test]
+fn group_rematch() {
+    let mut table = table(&[&[0, 1, 2], &[0, 1, 2]], &[&[0, 1], &[0, 1], &[1]]);
+    table.set_capacity(BigIndex::new(0), 2);
+    table.set_capacity(BigIndex::new(1), 3);
+    table.group_littles([LittleIndex::new(0), LittleIndex::new(1)]);
+    let previous = table.find_even_matching();
+    assert_eq!(
+        previous.pairs().collect::<Vec<_>>(),
+        [pair(0, 0), pair(0, 1), pair(1, 2)]
+    );
+    table.withdraw(BigIndex::new(0));
+    let rematching = table.rematch(&previous);
+    let moved = rematching
+        .diff()
+        .moved()
+        .iter()
+        .map(|m| (m.from, m.to, m.little))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        moved,
+        [
+            (BigIndex::new(0), BigIndex::new(1), LittleIndex::new(0)),
+            (BigIndex::new(0), BigIndex::new(1), LittleIndex::new(1))
+        ]
+    );
+}
+
+/// Checks that a group locked to bigs in different units is reported and left unmatched, and
+/// that the same locks are kept once the bigs share a unit.
+#[test]
+fn conflicting_group_locks() {
+    let both: &[u32] = &[0, 1];
+    let mut table = table(&[both, both], &[both, both]);
+    table.group_littles([LittleIndex::new(0), LittleIndex::new(1)]);
+    table.lock(BigIndex::new(0), LittleIndex::new(0));
+    table.lock(BigIndex::new(1), LittleIndex::new(1));
+    assert_eq!(
+        table.lock_conflicts(),
+        [[LittleIndex::new(0), LittleIndex::new(1)]]
+    );
+    assert_eq!(table.find_even_matching().pairs().count(), 0);
+    let feasibility = table.feasibility();
+    assert!(!feasibility.is_feasible());
+    assert!(feasibility.unmatchable().is_empty());
+    assert_eq!(feasibility.lock_conflicts(), table.lock_conflicts());
+    table.pair_co_bigs(BigIndex::new(0), BigIndex::new(1));
+    assert!(table.lock_conflicts().is_empty());
+    assert_eq!(
+        table.find_even_matching().pairs().collect::<Vec<_>>(),
+        [pair(0, 0), pair(1, 1)]
+    );
+}
@@ -0,0 +1,111 @@
+//! Matching History Tests
+
+use biglittle::{
+    history::{History, RepeatPolicy, Season},
+    Big, BigIndex, Little, LittleIndex, Names, PreferenceTable,
+};
+
+/// Builds the names and preferences of two bigs, Alice and Bob, and two littles, Dan and Erin,
+/// where everyone ranks Alice and Dan first.
+fn season() -> (Names<&'static str>, PreferenceTable) {
+    let mut names = Names::default();
+    let mut table = PreferenceTable::default();
+    for big in ["Alice", "Bob"] {
+        names.insert::<Big>(big);
+        table.insert::<Big, _>([LittleIndex::new(0), LittleIndex::new(1)]);
+    }
+    for little in ["Dan", "Erin"] {
+        names.insert::<Little>(little);
+        table.insert::<Little, _>([BigIndex::new(0), BigIndex::new(1)]);
+    }
+    (names, table)
+}
+
+/// Returns the history of one past season where Alice had Dan.
+fn history() -> History<&'static str> {
+    let mut history = History::default();
+    history.push(Season {
+        label: "Fall".to_string(),
+        pairs: vec![("Alice", "Dan"), ("Bob", "Erin")],
+    });
+    history
+}
+
+/// Checks that a season records the pairs of a matching by name.
+#[test]
+fn season_from_matching() {
+    let (names, table) = season();
+    let season = Season::new("Fall".to_string(), &table.find_even_matching(), &names);
+    assert_eq!(season.pairs, [("Alice", "Dan"), ("Bob", "Erin")]);
+    assert_eq!(history().count(&"Alice", &"Dan"), 1);
+    assert_eq!(history().count(&"Alice", &"Erin"), 0);
+}
+
+/// Checks that a season pairs every little of a co-big unit with each member of the unit.
+#[test]
+fn season_from_co_big_unit() {
+    let (names, mut table) = season();
+    table.pair_co_bigs(BigIndex::new(0), BigIndex::new(1));
+    let season = Season::new("Fall".to_string(), &table.find_even_matching(), &names);
+    assert_eq!(
+        season.pairs,
+        [
+            ("Alice", "Dan"),
+            ("Bob", "Dan"),
+            ("Bob", "Erin"),
+            ("Alice", "Erin")
+        ]
+    );
+}
+
+/// Checks that penalized repeat pairings move to the end of the preferences.
+#[test]
+fn repeats_are_penalized() {
+    let (names, mut table) = season();
+    history().apply(&mut table, &names, RepeatPolicy::Penalize);
+    assert_eq!(
+        table.find_even_matching().pairs().collect::<Vec<_>>(),
+        [
+            (BigIndex::new(0), LittleIndex::new(1)),
+            (BigIndex::new(1), LittleIndex::new(0))
+        ]
+    );
+}
+
+/// Checks that forbidden repeat pairings are never matched.
+#[test]
+fn repeats_are_forbidden() {
+    let (names, mut table) = season();
+    let mut history = history();
+    history.push(Season {
+        label: "Spring".to_string(),
+        pairs: vec![("Alice", "Erin"), ("Bob", "Dan")],
+    });
+    history.apply(&mut table, &names, RepeatPolicy::Forbid);
+    let matching_set = table.find_even_matching();
+    assert_eq!(matching_set.pairs().count(), 0);
+    assert_eq!(matching_set.unmatched_littles().len(), 2);
+}
+
+/// Checks that a little who already had someone from a family is matched into another family
+/// first, even without repeating the exact pairing.
+#[test]
+fn families_are_penalized() {
+    let (names, table) = season();
+    let mut history = History::default();
+    history.push(Season {
+        label: "Fall".to_string(),
+        pairs: vec![("Alice", "Carol"), ("Carol", "Dan")],
+    });
+    for policy in [RepeatPolicy::Penalize, RepeatPolicy::Forbid] {
+        let mut table = table.clone();
+        history.apply(&mut table, &names, policy);
+        assert_eq!(
+            table.find_even_matching().pairs().collect::<Vec<_>>(),
+            [
+                (BigIndex::new(0), LittleIndex::new(1)),
+                (BigIndex::new(1), LittleIndex::new(0))
+            ]
+        );
+    }
+}
@@ -0,0 +1,548 @@
+//! Matching Invariant Tests
+//!
+//! Generates random preference tables and checks that every [`MatchingSet`] produced by the
+//! solvers is well-formed.
+
+use biglittle::{Big, BigIndex, Little, LittleIndex, MatchingSet, PreferenceTable};
+use indexmap::IndexSet;
+use proptest::{
+    collection::vec,
+    prelude::*,
+    sample::{subsequence, Index},
+};
+
+/// Maximum Number of Bigs
+const MAX_BIGS: usize = 6;
+
+/// Maximum Number of Littles
+const MAX_LITTLES: usize = 10;
+
+/// Random Preference Table Input
+#[derive(Clone, Debug)]
+struct Input {
+    /// Big Preferences
+    bigs: Vec<Vec<u32>>,
+
+    /// Little Preferences
+    littles: Vec<Vec<u32>>,
+}
+
+impl Input {
+    /// Returns the number of bigs in the input.
+    fn big_count(&self) -> usize {
+        self.bigs.len()
+    }
+
+    /// Returns the number of littles in the input.
+    fn little_count(&self) -> usize {
+        self.littles.len()
+    }
+
+    /// Builds the [`PreferenceTable`] for this input.
+    fn table(&self) -> PreferenceTable {
+        let mut table = PreferenceTable::default();
+        for row in &self.bigs {
+            table.insert::<Big, _>(row.iter().map(|i| LittleIndex::new(*i)));
+        }
+        for row in &self.littles {
+            table.insert::<Little, _>(row.iter().map(|i| BigIndex::new(*i)));
+        }
+        table
+    }
+
+    /// Returns `true` if `big` and `little` rank each other.
+    fn is_mutual(&self, big: usize, little: usize) -> bool {
+        self.bigs[big].contains(&(little as u32)) && self.littles[little].contains(&(big as u32))
+    }
+
+    /// Returns the bigs that `little` can be matched with.
+    fn acceptable_bigs(&self, little: usize) -> Vec<usize> {
+        (0..self.big_count())
+            .filter(|big| self.is_mutual(*big, little))
+            .collect()
+    }
+}
+
+/// Returns a strategy for a random preference ordering over `count` participants.
+fn preferences(count: usize) -> impl Strategy<Value = Vec<u32>> {
+    subsequence((0..count as u32).collect::<Vec<_>>(), 0..=count).prop_shuffle()
+}
+
+/// Returns a strategy for random preference tables with at most `max_bigs` bigs and `max_littles`
+/// littles.
+fn input(max_bigs: usize, max_littles: usize) -> impl Strategy<Value = Input> {
+    (1..=max_bigs, 1..=max_littles).prop_flat_map(|(big_count, little_count)| {
+        (
+            vec(preferences(little_count), big_count),
+            vec(preferences(big_count), little_count),
+        )
+            .prop_map(|(bigs, littles)| Input { bigs, littles })
+    })
+}
+
+/// Returns the number of littles matched to each big in `matching_set`.
+fn loads(input: &Input, matching_set: &MatchingSet) -> Vec<usize> {
+    let mut loads = vec![0; input.big_count()];
+    for (big, load) in loads.iter_mut().enumerate() {
+        if let Some(matching) = matching_set
+            .matches()
+            .iter()
+            .find(|m| m.big == BigIndex::from(big))
+        {
+            *load = matching.littles.len();
+        }
+    }
+    loads
+}
+
+/// Finds the smallest possible largest load over every assignment of the `littles` to bigs they
+/// are mutually acceptable with.
+fn optimal_largest_load(input: &Input, littles: &[usize]) -> usize {
+    fn search(input: &Input, littles: &[usize], loads: &mut [usize], best: &mut usize) {
+        let current = loads.iter().copied().max().unwrap_or_default();
+        if current >= *best {
+            return;
+        }
+        match littles.split_first() {
+            Some((little, rest)) => {
+                for big in input.acceptable_bigs(*little) {
+                    loads[big] += 1;
+                    search(input, rest, loads, best);
+                    loads[big] -= 1;
+                }
+            }
+            _ => *best = current,
+        }
+    }
+    let mut best = usize::MAX;
+    search(input, littles, &mut vec![0; input.big_count()], &mut best);
+    best
+}
+
+/// Finds the largest number of littles that can be matched with mutually acceptable bigs without
+/// going over the `capacities` of the bigs.
+fn maximum_matched(input: &Input, capacities: &[usize]) -> usize {
+    fn search(
+        input: &Input,
+        capacities: &[usize],
+        little: usize,
+        loads: &mut [usize],
+        matched: usize,
+        best: &mut usize,
+    ) {
+        if matched + (input.little_count() - little) <= *best {
+            return;
+        }
+        if little == input.little_count() {
+            *best = matched;
+            return;
+        }
+        for big in input.acceptable_bigs(little) {
+            if loads[big] < capacities[big] {
+                loads[big] += 1;
+                search(input, capacities, little + 1, loads, matched + 1, best);
+                loads[big] -= 1;
+            }
+        }
+        search(input, capacities, little + 1, loads, matched, best);
+    }
+    let mut best = 0;
+    search(
+        input,
+        capacities,
+        0,
+        &mut vec![0; input.big_count()],
+        0,
+        &mut best,
+    );
+    best
+}
+
+/// Returns a strategy for random preference tables together with a capacity of at most
+/// `max_capacity` for each big.
+fn capacitated_input(
+    max_bigs: usize,
+    max_littles: usize,
+    max_capacity: usize,
+) -> impl Strategy<Value = (Input, Vec<usize>)> {
+    input(max_bigs, max_littles).prop_flat_map(move |input| {
+        let big_count = input.big_count();
+        (Just(input), vec(0..=max_capacity, big_count))
+    })
+}
+
+/// Returns a strategy for random capacitated preference tables together with a label for each big
+/// and each little, where the bigs with the same label share one co-big unit and the littles with
+/// the same label form one group.
+fn grouped_input(
+    max_bigs: usize,
+    max_littles: usize,
+    max_capacity: usize,
+) -> impl Strategy<Value = ((Input, Vec<usize>), Vec<usize>, Vec<usize>)> {
+    capacitated_input(max_bigs, max_littles, max_capacity).prop_flat_map(|(input, capacities)| {
+        let (big_count, little_count) = (input.big_count(), input.little_count());
+        (
+            Just((input, capacities)),
+            vec(0..big_count, big_count),
+            vec(0..little_count, little_count),
+        )
+    })
+}
+
+/// Builds the [`PreferenceTable`] for `input` with the `capacities`, pairing the bigs with the
+/// same `units` label and grouping the littles with the same `groups` label.
+fn grouped_table(
+    input: &Input,
+    capacities: &[usize],
+    units: &[usize],
+    groups: &[usize],
+) -> PreferenceTable {
+    let mut table = input.table();
+    for (big, capacity) in capacities.iter().enumerate() {
+        table.set_capacity(BigIndex::from(big), *capacity);
+    }
+    for label in 0..input.big_count() {
+        let mut members = (0..input.big_count()).filter(|big| units[*big] == label);
+        if let Some(first) = members.next() {
+            for member in members {
+                table.pair_co_bigs(BigIndex::from(first), BigIndex::from(member));
+            }
+        }
+    }
+    for label in 0..input.little_count() {
+        table.group_littles(
+            (0..input.little_count())
+                .filter(|little| groups[*little] == label)
+                .map(LittleIndex::from),
+        );
+    }
+    table
+}
+
+/// Asserts that every group of littles in `matching_set` is matched with one co-big unit of
+/// `table` or left unmatched together, and that no unit goes over the sum of the `capacities` of
+/// its members.
+fn assert_grouped(
+    input: &Input,
+    table: &PreferenceTable,
+    capacities: &[usize],
+    matching_set: &MatchingSet,
+) {
+    let unit = |little: LittleIndex| {
+        matching_set
+            .pairs()
+            .find(|(_, l)| *l == little)
+            .map(|(big, _)| table.unit(big))
+    };
+    for little in (0..input.little_count()).map(LittleIndex::from) {
+        for member in table.group(little) {
+            assert_eq!(
+                unit(little),
+                unit(member),
+                "Little {:?} is not matched with the unit of their group.",
+                member
+            );
+        }
+    }
+    let loads = loads(input, matching_set);
+    for big in (0..input.big_count()).map(BigIndex::from) {
+        let members = table.unit(big);
+        let load = members
+            .iter()
+            .map(|b| loads[big_index(*b, input)])
+            .sum::<usize>();
+        let capacity = members
+            .iter()
+            .map(|b| capacities[big_index(*b, input)])
+            .sum::<usize>();
+        assert!(
+            load <= capacity,
+            "Unit of {:?} is matched beyond its capacity.",
+            big
+        );
+    }
+}
+
+/// Asserts the invariants that every [`MatchingSet`] computed from `input` must satisfy.
+fn assert_well_formed(input: &Input, matching_set: &MatchingSet) {
+    let table = input.table();
+    let mut matched_bigs = IndexSet::<BigIndex>::new();
+    let mut matched_littles = IndexSet::<LittleIndex>::new();
+    for matching in matching_set.matches() {
+        assert!(
+            matched_bigs.insert(matching.big),
+            "Big {:?} appears in more than one matching.",
+            matching.big
+        );
+        assert!(
+            !matching.littles.is_empty(),
+            "Big {:?} has an empty matching.",
+            matching.big
+        );
+        assert!(
+            matching.littles.len() <= input.bigs[big_index(matching.big, input)].len(),
+            "Big {:?} is matched beyond its capacity.",
+            matching.big
+        );
+        for little in &matching.littles {
+            assert!(
+                matched_littles.insert(*little),
+                "Little {:?} appears more than once.",
+                little
+            );
+            assert!(
+                matching.big.preference(*little, &table).is_some()
+                    && little.preference(matching.big, &table).is_some(),
+                "Big {:?} and little {:?} are not mutually acceptable.",
+                matching.big,
+                little
+            );
+        }
+    }
+    for big in (0..input.big_count()).map(BigIndex::from) {
+        assert!(
+            matched_bigs.contains(&big) != matching_set.unmatched_bigs().contains(&big),
+            "Big {:?} must be either matched or unmatched.",
+            big
+        );
+    }
+    for little in (0..input.little_count()).map(LittleIndex::from) {
+        assert!(
+            matched_littles.contains(&little) != matching_set.unmatched_littles().contains(&little),
+            "Little {:?} must be either matched or unmatched.",
+            little
+        );
+    }
+    assert_eq!(
+        matched_bigs.len() + matching_set.unmatched_bigs().len(),
+        input.big_count()
+    );
+    assert_eq!(
+        matched_littles.len() + matching_set.unmatched_littles().len(),
+        input.little_count()
+    );
+}
+
+/// Returns the position of `big` in the bigs of `input`.
+fn big_index(big: BigIndex, input: &Input) -> usize {
+    (0..input.big_count())
+        .position(|i| BigIndex::from(i) == big)
+        .expect("Matched bigs must come from the input.")
+}
+
+/// Returns the position of `little` in the littles of `input`.
+fn little_index(little: LittleIndex, input: &Input) -> usize {
+    (0..input.little_count())
+        .position(|i| LittleIndex::from(i) == little)
+        .expect("Littles must come from the input.")
+}
+
+/// Returns the littles that can be matched with at least one big.
+fn matchable_littles(input: &Input) -> Vec<usize> {
+    (0..input.little_count())
+        .filter(|little| !input.acceptable_bigs(*little).is_empty())
+        .collect()
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(512))]
+
+    /// Checks that the maximal matching is well-formed.
+    #[test]
+    fn maximal_matching_is_well_formed(input in input(MAX_BIGS, MAX_LITTLES)) {
+        assert_well_formed(&input, &input.table().find_maximal_matching());
+    }
+
+    /// Checks that the maximal matching matches every little that has a mutually acceptable big,
+    /// and that each of those littles is matched with the first such big on their list.
+    #[test]
+    fn maximal_matching_gives_first_acceptable_choice(input in input(MAX_BIGS, MAX_LITTLES)) {
+        let matching_set = input.table().find_maximal_matching();
+        for (little, bigs) in input.littles.iter().enumerate() {
+            let expected = bigs
+                .iter()
+                .copied()
+                .find(|big| input.is_mutual(*big as usize, little))
+                .map(BigIndex::new);
+            let actual = matching_set
+                .matches()
+                .iter()
+                .find(|m| m.littles.contains(&LittleIndex::from(little)))
+                .map(|m| m.big);
+            prop_assert_eq!(expected, actual);
+        }
+    }
+
+    /// Checks that the even matching is well-formed.
+    #[test]
+    fn even_matching_is_well_formed(input in input(MAX_BIGS, MAX_LITTLES)) {
+        assert_well_formed(&input, &input.table().find_even_matching());
+    }
+
+    /// Checks that the even matching keeps every little that can be matched, matched.
+    #[test]
+    fn even_matching_keeps_matchable_littles(input in input(MAX_BIGS, MAX_LITTLES)) {
+        let matching_set = input.table().find_even_matching();
+        for little in matchable_littles(&input) {
+            prop_assert!(!matching_set.unmatched_littles().contains(&LittleIndex::from(little)));
+        }
+    }
+
+    /// Checks that every intermediate step of the even matching solver is well-formed and that the
+    /// last step agrees with the batch solver.
+    #[test]
+    fn even_matching_steps_end_at_batch_result(input in input(MAX_BIGS, MAX_LITTLES)) {
+        let table = input.table();
+        let mut last = None;
+        for matching_set in table.even_matching() {
+            assert_well_formed(&input, &matching_set);
+            last = Some(matching_set);
+        }
+        prop_assert_eq!(last, Some(table.find_even_matching()));
+    }
+
+    /// Checks that the largest matching in the even matching is as small as any assignment of the
+    /// matchable littles allows.
+    #[test]
+    fn even_matching_has_minimal_spread(input in input(4, 7)) {
+        let matching_set = input.table().find_even_matching();
+        let largest = loads(&input, &matching_set).into_iter().max().unwrap_or_default();
+        let littles = matchable_littles(&input);
+        let optimal = if littles.is_empty() { 0 } else { optimal_largest_load(&input, &littles) };
+        prop_assert_eq!(largest, optimal);
+    }
+
+    /// Checks that the even matching stays within the capacity of every big and still matches as
+    /// many littles as possible.
+    #[test]
+    fn even_matching_respects_capacities((input, capacities) in capacitated_input(4, 7, 2)) {
+        let mut table = input.table();
+        for (big, capacity) in capacities.iter().enumerate() {
+            table.set_capacity(BigIndex::from(big), *capacity);
+        }
+        let matching_set = table.find_even_matching();
+        assert_well_formed(&input, &matching_set);
+        for (load, capacity) in loads(&input, &matching_set).into_iter().zip(&capacities) {
+            prop_assert!(load <= *capacity);
+        }
+        prop_assert_eq!(
+            matching_set.pairs().count(),
+            maximum_matched(&input, &capacities)
+        );
+    }
+
+    /// Checks that the feasibility report finds the largest possible number of matched littles
+    /// and that every reported violation really is one.
+    #[test]
+    fn feasibility_is_exact((input, capacities) in capacitated_input(4, 7, 2)) {
+        let mut table = input.table();
+        for (big, capacity) in capacities.iter().enumerate() {
+            table.set_capacity(BigIndex::from(big), *capacity);
+        }
+        let feasibility = table.feasibility();
+        let maximum = maximum_matched(&input, &capacities);
+        prop_assert_eq!(feasibility.maximum_matched(), maximum);
+        prop_assert_eq!(feasibility.is_feasible(), maximum == input.little_count());
+        let mut deficit = feasibility.unmatchable().len();
+        for violation in feasibility.violations() {
+            let mut neighbors = IndexSet::new();
+            for little in violation.littles() {
+                let little = little_index(*little, &input);
+                for big in input.acceptable_bigs(little) {
+                    neighbors.insert(BigIndex::from(big));
+                }
+            }
+            neighbors.sort();
+            prop_assert_eq!(&neighbors, violation.bigs());
+            prop_assert!(violation.littles().len() > violation.capacity());
+            deficit += violation.deficit();
+        }
+        prop_assert_eq!(deficit, input.little_count() - maximum);
+    }
+
+    /// Checks that locking part of an even matching keeps those pairs in the next even matching
+    /// and still matches as many littles as possible.
+    #[test]
+    fn even_matching_keeps_locked_pairs(
+        ((input, capacities), mask) in (capacitated_input(4, 7, 2), vec(any::<bool>(), 7))
+    ) {
+        let mut table = input.table();
+        for (big, capacity) in capacities.iter().enumerate() {
+            table.set_capacity(BigIndex::from(big), *capacity);
+        }
+        let previous = table.find_even_matching();
+        let locked = previous
+            .pairs()
+            .filter(|(_, little)| mask[little_index(*little, &input)])
+            .collect::<Vec<_>>();
+        table.lock_pairs(locked.iter().copied());
+        let matching_set = table.find_even_matching();
+        assert_well_formed(&input, &matching_set);
+        for pair in locked {
+            prop_assert!(matching_set.pairs().any(|p| p == pair));
+        }
+        for (load, capacity) in loads(&input, &matching_set).into_iter().zip(&capacities) {
+            prop_assert!(load <= *capacity);
+        }
+        prop_assert_eq!(
+            matching_set.pairs().count(),
+            maximum_matched(&input, &capacities)
+        );
+    }
+
+    /// Checks that the solvers keep every pair mutually acceptable when bigs are paired into
+    /// co-big units and littles are grouped, and that every group stays together within the
+    /// capacity of its unit.
+    #[test]
+    fn units_and_groups_are_well_formed(
+        ((input, capacities), units, groups) in grouped_input(4, 7, 2)
+    ) {
+        let table = grouped_table(&input, &capacities, &units, &groups);
+        let matching_set = table.find_even_matching();
+        assert_well_formed(&input, &matching_set);
+        assert_grouped(&input, &table, &capacities, &matching_set);
+        prop_assert!(table.rematch(&matching_set).is_unchanged());
+        assert_well_formed(&input, &table.find_maximal_matching());
+    }
+
+    /// Checks that re-matching after a little withdraws only unmatches that little and still
+    /// matches as many littles as possible.
+    #[test]
+    fn rematch_after_withdrawal_keeps_pairs(
+        ((mut input, capacities), withdrawn) in (capacitated_input(4, 7, 2), any::<Index>())
+    ) {
+        let mut table = input.table();
+        for (big, capacity) in capacities.iter().enumerate() {
+            table.set_capacity(BigIndex::from(big), *capacity);
+        }
+        let previous = table.find_even_matching();
+        prop_assert!(table.rematch(&previous).is_unchanged());
+        let withdrawn = withdrawn.index(input.little_count());
+        table.withdraw(LittleIndex::from(withdrawn));
+        input.littles[withdrawn].clear();
+        for row in &mut input.bigs {
+            row.retain(|little| *little != withdrawn as u32);
+        }
+        let rematching = table.rematch(&previous);
+        let matching_set = rematching.matching_set();
+        assert_well_formed(&input, matching_set);
+        for (load, capacity) in loads(&input, matching_set).into_iter().zip(&capacities) {
+            prop_assert!(load <= *capacity);
+        }
+        prop_assert_eq!(
+            matching_set.pairs().count(),
+            maximum_matched(&input, &capacities)
+        );
+        for (_, little) in rematching.diff().removed() {
+            prop_assert_eq!(little_index(*little, &input), withdrawn);
+        }
+    }
+}
+
+/// Checks that an empty table produces an empty matching.
+#[test]
+fn empty_table_has_empty_matching() {
+    let table = PreferenceTable::default();
+    assert_eq!(table.find_maximal_matching(), MatchingSet::default());
+    assert_eq!(table.find_even_matching(), MatchingSet::default());
+}
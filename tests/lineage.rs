@@ -0,0 +1,80 @@
+//! Family Lineage Tests
+
+use biglittle::{
+    history::{History, Season},
+    lineage::Lineage,
+    Big, BigIndex, Little, LittleIndex, Names, PreferenceTable,
+};
+
+/// Builds a lineage where Zed had Alice and Xi, Alice had Quinn, and Yan had Bob.
+fn lineage() -> Lineage<&'static str> {
+    let mut history = History::default();
+    history.push(Season {
+        label: "Fall".to_string(),
+        pairs: vec![("Zed", "Alice"), ("Yan", "Bob"), ("Zed", "Xi")],
+    });
+    history.push(Season {
+        label: "Spring".to_string(),
+        pairs: vec![("Alice", "Quinn")],
+    });
+    Lineage::from_history(&history)
+}
+
+/// Checks the ancestors, descendants, and families of the lineage.
+#[test]
+fn relatives() {
+    let lineage = lineage();
+    assert_eq!(lineage.bigs("Alice"), [&"Zed"]);
+    assert_eq!(lineage.littles("Zed"), [&"Alice", &"Xi"]);
+    assert_eq!(lineage.ancestors("Quinn"), [&"Alice", &"Zed"]);
+    assert_eq!(lineage.descendants("Zed"), [&"Alice", &"Xi", &"Quinn"]);
+    assert_eq!(lineage.family("Xi"), [&"Zed", &"Alice", &"Xi", &"Quinn"]);
+    assert_eq!(
+        lineage.families(),
+        [
+            vec![&"Zed", &"Alice", &"Xi", &"Quinn"],
+            vec![&"Yan", &"Bob"]
+        ]
+    );
+    assert!(lineage.ancestors("Nobody").is_empty());
+}
+
+/// Checks that the lineage is written as a DOT graph with an edge from each big to each little.
+#[test]
+fn dot_export() {
+    let mut lineage = Lineage::default();
+    lineage.insert("Zed", "Al \"Junior\"");
+    assert_eq!(
+        lineage.dot().to_string(),
+        "digraph lineage {\n    \"Zed\";\n    \"Al \\\"Junior\\\"\";\n    \
+         \"Zed\" -> \"Al \\\"Junior\\\"\";\n}"
+    );
+}
+
+/// Checks that the even matching balances the family sizes once the families are applied.
+#[test]
+fn families_are_balanced() {
+    let mut names = Names::default();
+    let mut table = PreferenceTable::default();
+    for big in ["Alice", "Bob"] {
+        names.insert::<Big>(big);
+        table.insert::<Big, _>((0..4).map(LittleIndex::new));
+    }
+    for little in ["Dan", "Erin", "Finn", "Gus"] {
+        names.insert::<Little>(little);
+        table.insert::<Little, _>([BigIndex::new(0), BigIndex::new(1)]);
+    }
+    let loads = |table: &PreferenceTable| {
+        let matching_set = table.find_even_matching();
+        [0, 1].map(|i| {
+            matching_set
+                .matching(BigIndex::new(i))
+                .map_or(0, |m| m.littles.len())
+        })
+    };
+    assert_eq!(loads(&table), [2, 2]);
+    lineage().apply(&mut table, &names);
+    assert_eq!(table.family(BigIndex::new(0)), Some(0));
+    assert_eq!(table.family(BigIndex::new(1)), Some(1));
+    assert_eq!(loads(&table), [1, 3]);
+}
@@ -0,0 +1,34 @@
+//! Participant Metadata Tests
+
+use biglittle::{metadata::Metadata, Big, BigIndex, Little, LittleIndex};
+
+/// Checks that metadata is stored per kind and that fields are listed in first-seen order.
+#[test]
+fn metadata_is_stored_per_kind() {
+    let mut metadata = Metadata::default();
+    let big = BigIndex::new(0);
+    let little = LittleIndex::new(0);
+    assert_eq!(metadata.insert(big, "Email".into(), "a@x".into()), None);
+    assert_eq!(metadata.insert(big, "Major".into(), "Math".into()), None);
+    assert_eq!(
+        metadata.insert(big, "Email".into(), "b@x".into()),
+        Some("a@x".into())
+    );
+    metadata.insert(little, "Pledge Class".into(), "Alpha".into());
+    assert_eq!(metadata.get(big, "Email"), Some("b@x"));
+    assert_eq!(metadata.get(little, "Email"), None);
+    assert_eq!(
+        metadata.get(LittleIndex::new(0), "Pledge Class"),
+        Some("Alpha")
+    );
+    assert_eq!(
+        metadata.fields::<Big>().iter().collect::<Vec<_>>(),
+        ["Email", "Major"]
+    );
+    assert_eq!(
+        metadata.fields::<Little>().iter().collect::<Vec<_>>(),
+        ["Pledge Class"]
+    );
+    assert_eq!(metadata.record(BigIndex::new(1)), None);
+    assert_eq!(metadata.record(big).map(|r| r.len()), Some(2));
+}
@@ -0,0 +1,124 @@
+//! Generic Names Tests
+
+use biglittle::{
+    render::TableStyle, Big, BigIndex, Inconsistency, Little, LittleIndex, MatchingSet, Names,
+    PreferenceTable,
+};
+use core::fmt;
+
+/// Member Record
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+struct Member {
+    /// Name
+    name: &'static str,
+
+    /// Email
+    email: &'static str,
+}
+
+impl fmt::Display for Member {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} <{}>", self.name, self.email)
+    }
+}
+
+/// Checks that participants can be identified by numeric IDs.
+#[test]
+fn names_keyed_by_id() {
+    let mut names = Names::<u64>::default();
+    assert_eq!(names.insert::<Big>(1001), Some(BigIndex::new(0)));
+    assert_eq!(names.insert::<Little>(2001), Some(LittleIndex::new(0)));
+    assert_eq!(names.insert::<Little>(2002), Some(LittleIndex::new(1)));
+    assert_eq!(names.insert::<Little>(1001), None);
+    assert_eq!(names.index::<Little>(&2002), Some(LittleIndex::new(1)));
+    assert_eq!(names.get(BigIndex::new(0)), Some(&1001));
+    assert_eq!(names.len::<Little>(), 2);
+    assert_eq!(
+        names.iter::<Little>().collect::<Vec<_>>(),
+        vec![(LittleIndex::new(0), &2001), (LittleIndex::new(1), &2002)]
+    );
+    let mut table = PreferenceTable::default();
+    table.insert::<Big, _>([LittleIndex::new(1), LittleIndex::new(0)]);
+    table.insert::<Little, _>([BigIndex::new(0)]);
+    table.insert::<Little, _>([BigIndex::new(0)]);
+    assert_eq!(
+        table.find_even_matching().display(&names).to_string(),
+        "MatchingSet {\n    matches: {\n        1001: [2002, 2001],\n    },\n    \
+         unmatched_bigs:    []\n    unmatched_littles: [],\n}"
+    );
+}
+
+/// Checks that participants can be identified by a record and displayed through it.
+#[test]
+fn names_keyed_by_record() {
+    let alice = Member {
+        name: "Alice",
+        email: "alice@example.com",
+    };
+    let bob = Member {
+        name: "Bob",
+        email: "bob@example.com",
+    };
+    let mut names = Names::default();
+    let big = names.insert::<Big>(alice.clone()).unwrap();
+    let little = names.insert::<Little>(bob.clone()).unwrap();
+    assert_eq!(names.index::<Big>(&alice), Some(big));
+    assert_eq!(names.get(little), Some(&bob));
+    let mut table = PreferenceTable::default();
+    table.insert::<Big, _>([little]);
+    table.insert::<Little, _>([big]);
+    assert_eq!(
+        table.display(&names).to_string(),
+        "PreferenceTable {\n    bigs: {\n        Alice <alice@example.com>: \
+         [Bob <bob@example.com>],\n    },\n    littles: {\n        \
+         Bob <bob@example.com>: [Alice <alice@example.com>],\n    },\n}"
+    );
+}
+
+/// Checks that a table which does not match its names is reported by the consistency check and
+/// still displayed, with `#<index>` for the participants without a name.
+#[test]
+fn inconsistent_names() {
+    let mut names = Names::default();
+    names.insert::<Big>("Alice");
+    names.insert::<Little>("Dan");
+    let mut table = PreferenceTable::default();
+    table.insert::<Big, _>([LittleIndex::new(0)]);
+    table.insert::<Big, _>([LittleIndex::new(0)]);
+    table.insert::<Little, _>([BigIndex::new(1), BigIndex::new(0)]);
+    assert_eq!(
+        table.check_against(&names),
+        Err(Inconsistency::UnnamedBig(BigIndex::new(1)))
+    );
+    assert_eq!(
+        table.display(&names).to_string(),
+        "PreferenceTable {\n    bigs: {\n        Alice: [Dan],\n        #1:    [Dan],\n    },\n    \
+         littles: {\n        Dan: [#1, Alice],\n    },\n}"
+    );
+    let matching_set = table.find_even_matching();
+    assert!(matching_set.display(&names).to_string().contains("#1"));
+    assert!(matching_set
+        .table(&table, &names)
+        .display(TableStyle::Markdown)
+        .to_string()
+        .contains("| #1 "));
+    assert!(table.dot(&names).to_string().contains("[label = \"#1\"]"));
+    assert!(matching_set.html(&table, &names).to_string().contains("#1"));
+    names.insert::<Big>("Bob");
+    assert_eq!(table.check_against(&names), Ok(()));
+    table.insert::<Big, _>([LittleIndex::new(4)]);
+    names.insert::<Big>("Carol");
+    assert_eq!(
+        table.check_against(&names),
+        Err(Inconsistency::UnknownLittle {
+            big: BigIndex::new(2),
+            little: LittleIndex::new(4),
+        })
+    );
+    assert!(table.display(&names).to_string().contains("Carol: [#4]"));
+    let unknown = MatchingSet::from_parts([(BigIndex::new(7), LittleIndex::new(9))], [], []);
+    assert!(unknown
+        .html(&table, &names)
+        .to_string()
+        .contains("<td>#7</td><td>#9</td>"));
+}
@@ -0,0 +1,66 @@
+//! Incremental Re-Matching Tests
+
+mod common;
+
+use biglittle::{diff::Move, BigIndex, Little, LittleIndex};
+use common::{pair, table};
+
+/// Builds the move of the little at `little` from the big at `from` to the big at `to`.
+fn moved(little: u32, from: u32, to: u32) -> Move {
+    Move {
+        little: LittleIndex::new(little),
+        from: BigIndex::new(from),
+        to: BigIndex::new(to),
+    }
+}
+
+/// Checks that a late little is placed with the least-loaded big without moving anyone else.
+#[test]
+fn late_little_joins() {
+    let mut table = table(&[&[0, 1, 2], &[0, 1, 2]], &[&[0, 1], &[1, 0]]);
+    let previous = table.find_even_matching();
+    assert_eq!(
+        previous.pairs().collect::<Vec<_>>(),
+        [pair(0, 0), pair(1, 1)]
+    );
+    table.insert::<Little, _>([BigIndex::new(1), BigIndex::new(0)]);
+    let rematching = table.rematch(&previous);
+    assert_eq!(rematching.diff().added(), [pair(1, 2)]);
+    assert!(rematching.diff().removed().is_empty());
+    assert!(rematching.diff().moved().is_empty());
+    assert!(rematching.matching_set().unmatched_littles().is_empty());
+}
+
+/// Checks that the littles of a withdrawn big are matched again and everyone else stays put.
+#[test]
+fn big_withdraws() {
+    let mut table = table(&[&[0, 1], &[0, 1], &[2]], &[&[0, 1], &[1], &[2]]);
+    let previous = table.find_even_matching();
+    assert_eq!(
+        previous.pairs().collect::<Vec<_>>(),
+        [pair(0, 0), pair(1, 1), pair(2, 2)]
+    );
+    table.withdraw(BigIndex::new(0));
+    let rematching = table.rematch(&previous);
+    assert_eq!(rematching.diff().moved(), [moved(0, 0, 1)]);
+    assert!(rematching.diff().added().is_empty());
+    assert!(rematching
+        .matching_set()
+        .unmatched_bigs()
+        .contains(&BigIndex::new(0)));
+}
+
+/// Checks that an edited preference row can move a little along an augmenting path to make room.
+#[test]
+fn edited_preferences_shift_littles() {
+    let mut table = table(&[&[0, 1], &[0]], &[&[0, 1], &[]]);
+    table.set_capacity(BigIndex::new(0), 1);
+    let previous = table.find_even_matching();
+    assert_eq!(previous.pairs().collect::<Vec<_>>(), [pair(0, 0)]);
+    assert!(table.rematch(&previous).is_unchanged());
+    table.set_preferences(LittleIndex::new(1), [BigIndex::new(0)]);
+    let rematching = table.rematch(&previous);
+    assert_eq!(rematching.diff().added(), [pair(0, 1)]);
+    assert_eq!(rematching.diff().moved(), [moved(0, 0, 1)]);
+    assert!(rematching.diff().removed().is_empty());
+}
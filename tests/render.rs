@@ -0,0 +1,112 @@
+//! Table Rendering Tests
+
+use biglittle::{
+    render::{Alignment, Table, TableStyle},
+    Big, BigIndex, Little, LittleIndex, MatchingSet, Names, PreferenceTable,
+};
+
+/// Checks that tables are laid out as ASCII and Markdown with aligned and escaped cells.
+#[test]
+fn table_styles() {
+    let mut table = Table::new([("Name", Alignment::Left), ("Count", Alignment::Right)]);
+    table.push_row(["A|B", "7"]);
+    table.push_row(["Carol"]);
+    assert_eq!(table.rows()[1], ["Carol", ""]);
+    assert_eq!(
+        table.display(TableStyle::Ascii).to_string(),
+        "+-------+-------+\n\
+         | Name  | Count |\n\
+         +-------+-------+\n\
+         | A|B   |     7 |\n\
+         | Carol |       |\n\
+         +-------+-------+"
+    );
+    assert_eq!(
+        table.display(TableStyle::Markdown).to_string(),
+        "| Name  | Count |\n\
+         | ----- | ----: |\n\
+         | A\\|B  |     7 |\n\
+         | Carol |       |"
+    );
+}
+
+/// Checks the tables of preferences and of a matching with ranks and unmatched participants.
+#[test]
+fn matching_tables() {
+    let mut names = Names::default();
+    names.insert::<Big>("Alice");
+    names.insert::<Big>("Bob");
+    names.insert::<Little>("Dan");
+    names.insert::<Little>("Erin");
+    let mut table = PreferenceTable::default();
+    table.insert::<Big, _>([LittleIndex::new(1), LittleIndex::new(0)]);
+    table.insert::<Big, _>([]);
+    table.insert::<Little, _>([BigIndex::new(0)]);
+    table.insert::<Little, _>([]);
+    assert_eq!(
+        table
+            .table::<Big, _>(&names)
+            .display(TableStyle::Markdown)
+            .to_string(),
+        "| Big   | Rank 1 | Rank 2 |\n\
+         | ----- | ------ | ------ |\n\
+         | Alice | Erin   | Dan    |\n\
+         | Bob   |        |        |"
+    );
+    let matching_set = MatchingSet::from_parts(
+        [(BigIndex::new(0), LittleIndex::new(0))],
+        [BigIndex::new(1)],
+        [LittleIndex::new(1)],
+    );
+    assert_eq!(
+        matching_set
+            .table(&table, &names)
+            .display(TableStyle::Ascii)
+            .to_string(),
+        "+-------+--------+-------------+----------------+\n\
+         | Big   | Little | Rank by Big | Rank by Little |\n\
+         +-------+--------+-------------+----------------+\n\
+         | Alice | Dan    |           2 |              1 |\n\
+         | Bob   |        |             |                |\n\
+         |       | Erin   |             |                |\n\
+         +-------+--------+-------------+----------------+"
+    );
+}
+
+/// Checks that names are aligned by display width and truncated with an ellipsis.
+#[test]
+fn unicode_alignment() {
+    let mut names = Names::default();
+    names.insert::<Big>("José");
+    names.insert::<Big>("李小龍");
+    names.insert::<Little>("Zoë Ångström");
+    let mut table = PreferenceTable::default();
+    table.insert::<Big, _>([LittleIndex::new(0)]);
+    table.insert::<Big, _>([LittleIndex::new(0)]);
+    table.insert::<Little, _>([BigIndex::new(1), BigIndex::new(0)]);
+    assert_eq!(
+        table.display(&names).to_string(),
+        "PreferenceTable {\n    bigs: {\n        José:   [Zoë Ångström],\n        \
+         李小龍: [Zoë Ångström],\n    },\n    littles: {\n        \
+         Zoë Ångström: [李小龍, José],\n    },\n}"
+    );
+    assert_eq!(
+        table.display(&names).max_width(4).to_string(),
+        "PreferenceTable {\n    bigs: {\n        José: [Zoë…],\n        \
+         李…:  [Zoë…],\n    },\n    littles: {\n        \
+         Zoë…: [李…, José],\n    },\n}"
+    );
+    assert_eq!(
+        table
+            .table::<Big, _>(&names)
+            .display(TableStyle::Ascii)
+            .max_width(5)
+            .to_string(),
+        "+-------+--------+\n\
+         | Big   | Rank 1 |\n\
+         +-------+--------+\n\
+         | José  | Zoë …  |\n\
+         | 李小… | Zoë …  |\n\
+         +-------+--------+"
+    );
+}
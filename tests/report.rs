@@ -0,0 +1,77 @@
+//! Stability and HTML Report Tests
+
+use biglittle::{Big, BigIndex, Little, LittleIndex, MatchingSet, Names, PreferenceTable};
+
+/// Builds a table where Alice and Bob both want Dan first, and Dan and Erin both want Alice first.
+fn table() -> PreferenceTable {
+    let mut table = PreferenceTable::default();
+    table.insert::<Big, _>([LittleIndex::new(0), LittleIndex::new(1)]);
+    table.insert::<Big, _>([LittleIndex::new(0), LittleIndex::new(1)]);
+    table.insert::<Little, _>([BigIndex::new(0), BigIndex::new(1)]);
+    table.insert::<Little, _>([BigIndex::new(0), BigIndex::new(1)]);
+    table.set_capacity(BigIndex::new(0), 1);
+    table.set_capacity(BigIndex::new(1), 1);
+    table
+}
+
+/// Builds a matching set of Alice with Erin and Bob with Dan.
+fn crossed() -> MatchingSet {
+    MatchingSet::from_parts(
+        [
+            (BigIndex::new(0), LittleIndex::new(1)),
+            (BigIndex::new(1), LittleIndex::new(0)),
+        ],
+        [],
+        [],
+    )
+}
+
+/// Checks that only pairs who both prefer each other to their partners block a matching.
+#[test]
+fn blocking_pairs() {
+    let mut table = table();
+    assert_eq!(
+        crossed().blocking_pairs(&table),
+        [(BigIndex::new(0), LittleIndex::new(0))]
+    );
+    assert!(table.find_even_matching().blocking_pairs(&table).is_empty());
+    table.set_capacity(BigIndex::new(0), 2);
+    let unmatched = MatchingSet::from_parts(
+        [(BigIndex::new(0), LittleIndex::new(0))],
+        [BigIndex::new(1)],
+        [LittleIndex::new(1)],
+    );
+    assert_eq!(
+        unmatched.blocking_pairs(&table),
+        [
+            (BigIndex::new(0), LittleIndex::new(1)),
+            (BigIndex::new(1), LittleIndex::new(1))
+        ]
+    );
+    table.lock(BigIndex::new(1), LittleIndex::new(0));
+    assert!(crossed().blocking_pairs(&table).is_empty());
+}
+
+/// Checks that the report lists every pair with its ranks, escapes names, and has no external
+/// assets.
+#[test]
+fn html_report() {
+    let mut names = Names::default();
+    names.insert::<Big>("Alice & Co");
+    names.insert::<Big>("Bob");
+    names.insert::<Little>("<Dan>");
+    names.insert::<Little>("Erin");
+    let report = crossed().html(&table(), &names).to_string();
+    assert!(report.starts_with("<!DOCTYPE html>"));
+    assert!(report.contains(
+        "<tr><td>Alice &amp; Co</td><td>Erin</td><td class=\"rank\">2</td>\
+         <td class=\"rank\">1</td></tr>"
+    ));
+    assert!(report.contains(
+        "<tr><td>Bob</td><td>&lt;Dan&gt;</td><td class=\"rank\">1</td>\
+         <td class=\"rank\">2</td></tr>"
+    ));
+    assert!(report.contains("<tr><th>Blocking Pairs</th><td class=\"rank\">1</td></tr>"));
+    assert!(report.contains("<h2>Unmatched Littles</h2>\n<p>None.</p>"));
+    assert!(!report.contains("src=") && !report.contains("href="));
+}
@@ -0,0 +1,50 @@
+//! Preference Statistics Tests
+
+mod common;
+
+use biglittle::{Big, BigIndex, Little, LittleIndex};
+use common::table;
+
+/// Checks that every ranking is counted at its position.
+#[test]
+fn rank_positions_are_counted() {
+    let statistics = table(&[&[0, 1], &[1]], &[&[0, 1], &[1, 0], &[0]]).statistics();
+    let big = statistics.rank_counts(BigIndex::new(0)).unwrap();
+    assert_eq!(big.positions(), [2, 1]);
+    assert_eq!((big.total(), big.at(1), big.at(2), big.at(3)), (3, 2, 1, 0));
+    let little = statistics.rank_counts(LittleIndex::new(1)).unwrap();
+    assert_eq!(little.positions(), [1, 1]);
+    let little = statistics.rank_counts(LittleIndex::new(2)).unwrap();
+    assert_eq!(little.total(), 0);
+    assert_eq!(statistics.longest_ranking::<Big>(), 2);
+    assert_eq!(statistics.longest_ranking::<Little>(), 2);
+}
+
+/// Checks that mutual pairs are found and that participants without any are reported.
+#[test]
+fn mutual_pairs_are_found() {
+    let statistics = table(&[&[0, 1], &[1], &[]], &[&[0, 1], &[1, 0], &[0]]).statistics();
+    assert_eq!(
+        statistics.mutual_pairs(),
+        [
+            (BigIndex::new(0), LittleIndex::new(0)),
+            (BigIndex::new(0), LittleIndex::new(1)),
+            (BigIndex::new(1), LittleIndex::new(1)),
+        ]
+    );
+    assert_eq!(
+        statistics.without_mutual::<Big>().collect::<Vec<_>>(),
+        [BigIndex::new(2)]
+    );
+    assert_eq!(
+        statistics.without_mutual::<Little>().collect::<Vec<_>>(),
+        [LittleIndex::new(2)]
+    );
+    assert_eq!(
+        statistics
+            .rank_counts(LittleIndex::new(1))
+            .unwrap()
+            .mutual(),
+        2
+    );
+}
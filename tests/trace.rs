@@ -0,0 +1,121 @@
+//! Matching Trace Tests
+
+mod common;
+
+use biglittle::{
+    trace::{Event, Trace},
+    BigIndex, LittleIndex,
+};
+use common::table;
+
+/// Checks that tracing does not change the outcome of the solvers.
+#[test]
+fn trace_does_not_change_matching() {
+    let table = table(&[&[0, 1, 2], &[2, 1], &[]], &[&[0], &[0, 1], &[0, 1], &[2]]);
+    assert_eq!(
+        table.find_maximal_matching_with_observer(&mut Trace::default()),
+        table.find_maximal_matching()
+    );
+    assert_eq!(
+        table.find_even_matching_with_observer(&mut Trace::default()),
+        table.find_even_matching()
+    );
+}
+
+/// Checks the full history of a little who is rejected by their first choice.
+#[test]
+fn rejection_is_recorded() {
+    let table = table(&[&[1, 0], &[]], &[&[0], &[1, 0]]);
+    let mut trace = Trace::default();
+    let matching_set = table.find_even_matching_with_observer(&mut trace);
+    let (little, first, second) = (LittleIndex::new(1), BigIndex::new(1), BigIndex::new(0));
+    assert_eq!(
+        trace.events_for(little).copied().collect::<Vec<_>>(),
+        vec![
+            Event::Proposal { little, big: first },
+            Event::Rejection { little, big: first },
+            Event::Proposal {
+                little,
+                big: second
+            },
+            Event::Acceptance {
+                little,
+                big: second
+            },
+            Event::Matched {
+                little,
+                big: second
+            },
+        ]
+    );
+    assert_eq!(
+        trace.events_for(first).last(),
+        Some(&Event::UnmatchedBig { big: first })
+    );
+    assert_eq!(matching_set.matches().len(), 1);
+}
+
+/// Checks that shifts and final decisions are recorded for both the little and the bigs.
+#[test]
+fn shifts_are_recorded() {
+    let table = table(&[&[0, 1], &[1], &[]], &[&[0], &[0, 1], &[2]]);
+    let mut trace = Trace::default();
+    table.find_even_matching_with_observer(&mut trace);
+    let (little, from, to) = (LittleIndex::new(1), BigIndex::new(0), BigIndex::new(1));
+    let shift = Event::Shift { little, from, to };
+    assert!(trace.events_for(little).any(|e| *e == shift));
+    assert!(trace.events_for(from).any(|e| *e == shift));
+    assert!(trace.events_for(to).any(|e| *e == shift));
+    let little = LittleIndex::new(2);
+    assert_eq!(
+        trace.events_for(little).copied().collect::<Vec<_>>(),
+        vec![
+            Event::Proposal {
+                little,
+                big: BigIndex::new(2)
+            },
+            Event::Rejection {
+                little,
+                big: BigIndex::new(2)
+            },
+            Event::Exhaustion { little },
+            Event::UnmatchedLittle { little },
+        ]
+    );
+}
+
+/// Checks that the events of co-big units and little groups are reported for every member.
+#[test]
+fn groups_are_expanded() {
+    let mut table = table(&[&[0, 1], &[2], &[]], &[&[0], &[0], &[1]]);
+    table.pair_co_bigs(BigIndex::new(0), BigIndex::new(1));
+    table.group_littles([LittleIndex::new(0), LittleIndex::new(1)]);
+    let mut trace = Trace::default();
+    table.find_even_matching_with_observer(&mut trace);
+    let (little, big) = (LittleIndex::new(1), BigIndex::new(0));
+    assert_eq!(
+        trace.events_for(little).copied().collect::<Vec<_>>(),
+        vec![
+            Event::Proposal { little, big },
+            Event::Acceptance { little, big },
+            Event::Matched { little, big },
+        ]
+    );
+    let (little, big) = (LittleIndex::new(2), BigIndex::new(1));
+    assert_eq!(
+        trace.events_for(big).copied().collect::<Vec<_>>(),
+        vec![
+            Event::Proposal { little, big },
+            Event::Acceptance { little, big },
+            Event::Matched { little, big },
+        ]
+    );
+    let mut trace = Trace::default();
+    table.find_maximal_matching_with_observer(&mut trace);
+    assert!(trace
+        .events_for(big)
+        .any(|e| *e == Event::Matched { little, big }));
+    assert!(trace
+        .events_for(little)
+        .all(|e| !e.contains_big(BigIndex::new(0))));
+}